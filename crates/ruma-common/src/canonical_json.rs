@@ -8,7 +8,12 @@ use serde_json::Value as JsonValue;
 mod value;
 
 pub use self::value::{CanonicalJsonObject, CanonicalJsonValue};
-use crate::{serde::Raw, RoomVersionId};
+pub use crate::redaction::{JsonType, RedactionError};
+use crate::{
+    redaction::{allowed_content_keys_for, retain_keys},
+    serde::Raw,
+    RoomVersionId,
+};
 
 /// The set of possible errors when serializing to canonical JSON.
 #[cfg(feature = "canonical-json")]
@@ -35,72 +40,6 @@ impl fmt::Display for CanonicalJsonError {
 
 impl std::error::Error for CanonicalJsonError {}
 
-/// Errors that can happen in redaction.
-#[cfg(feature = "canonical-json")]
-#[derive(Debug)]
-#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
-pub enum RedactionError {
-    /// The field `field` is not of the correct type `of_type` ([`JsonType`]).
-    #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
-    NotOfType {
-        /// The field name.
-        field: String,
-        /// The expected JSON type.
-        of_type: JsonType,
-    },
-
-    /// The given required field is missing from a JSON object.
-    JsonFieldMissingFromObject(String),
-}
-
-impl fmt::Display for RedactionError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            RedactionError::NotOfType { field, of_type } => {
-                write!(f, "Value in {field:?} must be a JSON {of_type:?}")
-            }
-            RedactionError::JsonFieldMissingFromObject(field) => {
-                write!(f, "JSON object must contain the field {field:?}")
-            }
-        }
-    }
-}
-
-impl std::error::Error for RedactionError {}
-
-impl RedactionError {
-    fn not_of_type(target: &str, of_type: JsonType) -> Self {
-        Self::NotOfType { field: target.to_owned(), of_type }
-    }
-
-    fn field_missing_from_object(target: &str) -> Self {
-        Self::JsonFieldMissingFromObject(target.to_owned())
-    }
-}
-
-/// A JSON type enum for [`RedactionError`] variants.
-#[derive(Debug)]
-#[allow(clippy::exhaustive_enums)]
-pub enum JsonType {
-    /// A JSON Object.
-    Object,
-
-    /// A JSON String.
-    String,
-
-    /// A JSON Integer.
-    Integer,
-
-    /// A JSON Array.
-    Array,
-
-    /// A JSON Boolean.
-    Boolean,
-
-    /// JSON Null.
-    Null,
-}
-
 /// Fallible conversion from a `serde_json::Map` to a `CanonicalJsonObject`.
 pub fn try_from_json_map(
     json: serde_json::Map<String, JsonValue>,
@@ -193,12 +132,12 @@ pub fn redact_in_place(
             _ => return Err(RedactionError::not_of_type("content", JsonType::Object)),
         };
 
-        object_retain_keys(content, allowed_content_keys)?;
+        retain_keys(content, allowed_content_keys)?;
     }
 
     let mut old_event = mem::take(event);
 
-    for &key in allowed_event_keys_for(version) {
+    for &key in event_redaction_keys(version) {
         if let Some(value) = old_event.remove(key) {
             event.insert(key.to_owned(), value);
         }
@@ -223,62 +162,15 @@ pub fn redact_content_in_place(
     version: &RoomVersionId,
     event_type: impl AsRef<str>,
 ) -> Result<(), RedactionError> {
-    object_retain_keys(object, allowed_content_keys_for(event_type.as_ref(), version))
+    retain_keys(object, allowed_content_keys_for(event_type.as_ref(), version))
 }
 
-fn object_retain_keys(
-    object: &mut CanonicalJsonObject,
-    allowed_keys: &AllowedKeys,
-) -> Result<(), RedactionError> {
-    match *allowed_keys {
-        AllowedKeys::All => {}
-        AllowedKeys::Some { keys, nested } => {
-            object_retain_some_keys(object, keys, nested)?;
-        }
-        AllowedKeys::None => {
-            object.clear();
-        }
-    }
-
-    Ok(())
-}
-
-fn object_retain_some_keys(
-    object: &mut CanonicalJsonObject,
-    keys: &[&str],
-    nested: &[(&str, &AllowedKeys)],
-) -> Result<(), RedactionError> {
-    let mut old_object = mem::take(object);
-
-    for &(nested_key, nested_allowed_keys) in nested {
-        if let Some((key, mut nested_object_value)) = old_object.remove_entry(nested_key) {
-            let nested_object = match &mut nested_object_value {
-                CanonicalJsonValue::Object(map) => map,
-                _ => return Err(RedactionError::not_of_type(nested_key, JsonType::Object)),
-            };
-
-            object_retain_keys(nested_object, nested_allowed_keys)?;
-
-            // If the object is empty, it means none of the nested fields were found so we
-            // don't want to keep the object.
-            if !nested_object.is_empty() {
-                object.insert(key, nested_object_value);
-            }
-        }
-    }
-
-    for &key in keys {
-        if let Some((key, value)) = old_object.remove_entry(key) {
-            object.insert(key, value);
-        }
-    }
-
-    Ok(())
-}
-
-/// The fields that are allowed to remain in an event during redaction depending on the room
+/// The top-level keys of an event that are preserved during redaction, depending on the room
 /// version.
-fn allowed_event_keys_for(version: &RoomVersionId) -> &'static [&'static str] {
+///
+/// These keys are the same for all event types. The keys preserved inside `content` depend on the
+/// event type, use [`redact_content_in_place()`] to apply them.
+pub fn event_redaction_keys(version: &RoomVersionId) -> &'static [&'static str] {
     match version {
         RoomVersionId::V1
         | RoomVersionId::V2
@@ -323,180 +215,6 @@ fn allowed_event_keys_for(version: &RoomVersionId) -> &'static [&'static str] {
     }
 }
 
-/// List of keys to preserve on an object.
-#[derive(Clone, Copy)]
-enum AllowedKeys {
-    /// All keys are preserved.
-    All,
-    /// Some keys are preserved.
-    Some {
-        /// The keys to preserve on this object.
-        keys: &'static [&'static str],
-
-        /// Keys to preserve on nested objects.
-        ///
-        /// A list of `(nested_object_key, nested_allowed_keys)`.
-        nested: &'static [(&'static str, &'static AllowedKeys)],
-    },
-    /// No keys are preserved.
-    None,
-}
-
-impl AllowedKeys {
-    /// Creates an new `AllowedKeys::Some` with the given keys at this level.
-    const fn some(keys: &'static [&'static str]) -> Self {
-        Self::Some { keys, nested: &[] }
-    }
-
-    /// Creates an new `AllowedKeys::Some` with the given keys and nested keys.
-    const fn some_nested(
-        keys: &'static [&'static str],
-        nested: &'static [(&'static str, &'static AllowedKeys)],
-    ) -> Self {
-        Self::Some { keys, nested }
-    }
-}
-
-/// Allowed keys in `m.room.member`'s content according to room version 1.
-static ROOM_MEMBER_V1: AllowedKeys = AllowedKeys::some(&["membership"]);
-/// Allowed keys in `m.room.member`'s content according to room version 9.
-static ROOM_MEMBER_V9: AllowedKeys =
-    AllowedKeys::some(&["membership", "join_authorised_via_users_server"]);
-/// Allowed keys in `m.room.member`'s content according to room version 11.
-static ROOM_MEMBER_V11: AllowedKeys = AllowedKeys::some_nested(
-    &["membership", "join_authorised_via_users_server"],
-    &[("third_party_invite", &ROOM_MEMBER_THIRD_PARTY_INVITE_V11)],
-);
-/// Allowed keys in the `third_party_invite` field of `m.room.member`'s content according to room
-/// version 11.
-static ROOM_MEMBER_THIRD_PARTY_INVITE_V11: AllowedKeys = AllowedKeys::some(&["signed"]);
-
-/// Allowed keys in `m.room.create`'s content according to room version 1.
-static ROOM_CREATE_V1: AllowedKeys = AllowedKeys::some(&["creator"]);
-
-/// Allowed keys in `m.room.join_rules`'s content according to room version 1.
-static ROOM_JOIN_RULES_V1: AllowedKeys = AllowedKeys::some(&["join_rule"]);
-/// Allowed keys in `m.room.join_rules`'s content according to room version 8.
-static ROOM_JOIN_RULES_V8: AllowedKeys = AllowedKeys::some(&["join_rule", "allow"]);
-
-/// Allowed keys in `m.room.power_levels`'s content according to room version 1.
-static ROOM_POWER_LEVELS_V1: AllowedKeys = AllowedKeys::some(&[
-    "ban",
-    "events",
-    "events_default",
-    "kick",
-    "redact",
-    "state_default",
-    "users",
-    "users_default",
-]);
-/// Allowed keys in `m.room.power_levels`'s content according to room version 11.
-static ROOM_POWER_LEVELS_V11: AllowedKeys = AllowedKeys::some(&[
-    "ban",
-    "events",
-    "events_default",
-    "invite",
-    "kick",
-    "redact",
-    "state_default",
-    "users",
-    "users_default",
-]);
-
-/// Allowed keys in `m.room.aliases`'s content according to room version 1.
-static ROOM_ALIASES_V1: AllowedKeys = AllowedKeys::some(&["aliases"]);
-
-/// Allowed keys in `m.room.server_acl`'s content according to MSC2870.
-#[cfg(feature = "unstable-msc2870")]
-static ROOM_SERVER_ACL_MSC2870: AllowedKeys =
-    AllowedKeys::some(&["allow", "deny", "allow_ip_literals"]);
-
-/// Allowed keys in `m.room.history_visibility`'s content according to room version 1.
-static ROOM_HISTORY_VISIBILITY_V1: AllowedKeys = AllowedKeys::some(&["history_visibility"]);
-
-/// Allowed keys in `m.room.redaction`'s content according to room version 11.
-static ROOM_REDACTION_V11: AllowedKeys = AllowedKeys::some(&["redacts"]);
-
-fn allowed_content_keys_for(event_type: &str, version: &RoomVersionId) -> &'static AllowedKeys {
-    match event_type {
-        "m.room.member" => match version {
-            RoomVersionId::V1
-            | RoomVersionId::V2
-            | RoomVersionId::V3
-            | RoomVersionId::V4
-            | RoomVersionId::V5
-            | RoomVersionId::V6
-            | RoomVersionId::V7
-            | RoomVersionId::V8 => &ROOM_MEMBER_V1,
-            RoomVersionId::V9 | RoomVersionId::V10 => &ROOM_MEMBER_V9,
-            _ => &ROOM_MEMBER_V11,
-        },
-        "m.room.create" => match version {
-            RoomVersionId::V1
-            | RoomVersionId::V2
-            | RoomVersionId::V3
-            | RoomVersionId::V4
-            | RoomVersionId::V5
-            | RoomVersionId::V6
-            | RoomVersionId::V7
-            | RoomVersionId::V8
-            | RoomVersionId::V9
-            | RoomVersionId::V10 => &ROOM_CREATE_V1,
-            _ => &AllowedKeys::All,
-        },
-        "m.room.join_rules" => match version {
-            RoomVersionId::V1
-            | RoomVersionId::V2
-            | RoomVersionId::V3
-            | RoomVersionId::V4
-            | RoomVersionId::V5
-            | RoomVersionId::V6
-            | RoomVersionId::V7 => &ROOM_JOIN_RULES_V1,
-            _ => &ROOM_JOIN_RULES_V8,
-        },
-        "m.room.power_levels" => match version {
-            RoomVersionId::V1
-            | RoomVersionId::V2
-            | RoomVersionId::V3
-            | RoomVersionId::V4
-            | RoomVersionId::V5
-            | RoomVersionId::V6
-            | RoomVersionId::V7
-            | RoomVersionId::V8
-            | RoomVersionId::V9
-            | RoomVersionId::V10 => &ROOM_POWER_LEVELS_V1,
-            _ => &ROOM_POWER_LEVELS_V11,
-        },
-        "m.room.aliases" => match version {
-            RoomVersionId::V1
-            | RoomVersionId::V2
-            | RoomVersionId::V3
-            | RoomVersionId::V4
-            | RoomVersionId::V5 => &ROOM_ALIASES_V1,
-            // All other room versions, including custom ones, are treated by version 6 rules.
-            // TODO: Should we return an error for unknown versions instead?
-            _ => &AllowedKeys::None,
-        },
-        #[cfg(feature = "unstable-msc2870")]
-        "m.room.server_acl" if version.as_str() == "org.matrix.msc2870" => &ROOM_SERVER_ACL_MSC2870,
-        "m.room.history_visibility" => &ROOM_HISTORY_VISIBILITY_V1,
-        "m.room.redaction" => match version {
-            RoomVersionId::V1
-            | RoomVersionId::V2
-            | RoomVersionId::V3
-            | RoomVersionId::V4
-            | RoomVersionId::V5
-            | RoomVersionId::V6
-            | RoomVersionId::V7
-            | RoomVersionId::V8
-            | RoomVersionId::V9
-            | RoomVersionId::V10 => &AllowedKeys::None,
-            _ => &ROOM_REDACTION_V11,
-        },
-        _ => &AllowedKeys::None,
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
@@ -508,7 +226,8 @@ mod tests {
     };
 
     use super::{
-        redact_in_place, to_canonical_value, try_from_json_map, value::CanonicalJsonValue,
+        event_redaction_keys, redact_in_place, to_canonical_value, try_from_json_map,
+        value::CanonicalJsonValue,
     };
     use crate::RoomVersionId;
 
@@ -601,6 +320,20 @@ mod tests {
         assert_eq!(to_canonical_value(t).unwrap(), CanonicalJsonValue::Object(expected));
     }
 
+    #[test]
+    fn event_redaction_keys_depend_on_version() {
+        let v10_keys = event_redaction_keys(&RoomVersionId::V10);
+        assert!(v10_keys.contains(&"content"));
+        assert!(v10_keys.contains(&"origin"));
+        assert!(v10_keys.contains(&"membership"));
+
+        let v11_keys = event_redaction_keys(&RoomVersionId::V11);
+        assert!(v11_keys.contains(&"content"));
+        assert!(v11_keys.contains(&"depth"));
+        assert!(!v11_keys.contains(&"origin"));
+        assert!(!v11_keys.contains(&"membership"));
+    }
+
     #[test]
     fn redact_allowed_keys_some() {
         let original_event = json!({
@@ -30,7 +30,7 @@ pub use self::{
     room_alias_id::{OwnedRoomAliasId, RoomAliasId},
     room_id::{OwnedRoomId, RoomId},
     room_or_alias_id::{OwnedRoomOrAliasId, RoomOrAliasId},
-    room_version_id::RoomVersionId,
+    room_version_id::{EventIdFormat, RoomVersionId},
     server_name::{OwnedServerName, ServerName},
     session_id::{OwnedSessionId, SessionId},
     signatures::{DeviceSignatures, EntitySignatures, ServerSignatures, Signatures},
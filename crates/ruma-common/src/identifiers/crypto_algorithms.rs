@@ -1,5 +1,7 @@
 //! Key algorithms used in Matrix spec.
 
+use std::{convert::Infallible, str::FromStr};
+
 use ruma_macros::StringEnum;
 
 use crate::PrivOwnedStr;
@@ -36,6 +38,17 @@ pub enum SigningKeyAlgorithm {
     _Custom(PrivOwnedStr),
 }
 
+/// Parsing never fails, unknown algorithms are stored as custom values.
+///
+/// This allows to use [`KeyId::algorithm()`](super::KeyId::algorithm) with signing key IDs.
+impl FromStr for SigningKeyAlgorithm {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.into())
+    }
+}
+
 /// An encryption algorithm to be used to encrypt messages sent to a room.
 #[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/doc/string_enum.md"))]
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, StringEnum)]
@@ -83,6 +96,7 @@ mod tests {
     #[test]
     fn parse_signing_key_algorithm() {
         assert_eq!(SigningKeyAlgorithm::from("ed25519"), SigningKeyAlgorithm::Ed25519);
+        assert_eq!("ed25519".parse(), Ok(SigningKeyAlgorithm::Ed25519));
     }
 
     #[test]
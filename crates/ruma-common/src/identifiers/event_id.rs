@@ -2,7 +2,7 @@
 
 use ruma_macros::IdZst;
 
-use super::ServerName;
+use super::{EventIdFormat, ServerName};
 
 /// A Matrix [event ID].
 ///
@@ -69,6 +69,28 @@ impl EventId {
         self.colon_idx().map(|idx| ServerName::from_borrowed(&self.as_str()[idx + 1..]))
     }
 
+    /// Whether this event ID has the given format.
+    ///
+    /// Use [`RoomVersionId::event_id_format()`] to get the format of the event IDs in a room.
+    ///
+    /// [`RoomVersionId::event_id_format()`]: super::RoomVersionId::event_id_format
+    pub fn has_format(&self, format: EventIdFormat) -> bool {
+        if format == EventIdFormat::Original {
+            return self.colon_idx().is_some();
+        }
+
+        // The reference hash is a SHA-256 hash encoded with unpadded Base64.
+        let hash = &self.as_str()[1..];
+        hash.len() == 43
+            && hash.bytes().all(|b| {
+                b.is_ascii_alphanumeric()
+                    || match format {
+                        EventIdFormat::Base64 => b == b'+' || b == b'/',
+                        _ => b == b'-' || b == b'_',
+                    }
+            })
+    }
+
     fn colon_idx(&self) -> Option<usize> {
         self.as_str().find(':')
     }
@@ -77,7 +99,7 @@ impl EventId {
 #[cfg(test)]
 mod tests {
     use super::{EventId, OwnedEventId};
-    use crate::IdParseError;
+    use crate::{EventIdFormat, IdParseError};
 
     #[test]
     fn valid_original_event_id() {
@@ -243,4 +265,26 @@ mod tests {
             IdParseError::InvalidServerName
         );
     }
+
+    #[test]
+    fn event_id_has_format() {
+        let original = <&EventId>::try_from("$39hvsi03hlne:example.com").unwrap();
+        assert!(original.has_format(EventIdFormat::Original));
+        assert!(!original.has_format(EventIdFormat::Base64));
+        assert!(!original.has_format(EventIdFormat::UrlSafeBase64));
+
+        let base64 = <&EventId>::try_from("$acR1l0raoZnm60CBwAVgqbZqoO/mYU81xysh1u7XcJk").unwrap();
+        assert!(!base64.has_format(EventIdFormat::Original));
+        assert!(base64.has_format(EventIdFormat::Base64));
+        assert!(!base64.has_format(EventIdFormat::UrlSafeBase64));
+
+        let url_safe =
+            <&EventId>::try_from("$Rqnc-F-dvnEYJTyHq_iKxU2bZ1CI92-kuZq3a5lr5Zg").unwrap();
+        assert!(!url_safe.has_format(EventIdFormat::Original));
+        assert!(!url_safe.has_format(EventIdFormat::Base64));
+        assert!(url_safe.has_format(EventIdFormat::UrlSafeBase64));
+
+        let too_short = <&EventId>::try_from("$Rqnc-F-dvnEYJTyHq").unwrap();
+        assert!(!too_short.has_format(EventIdFormat::UrlSafeBase64));
+    }
 }
@@ -7,7 +7,7 @@ use std::{
 
 use ruma_macros::IdZst;
 
-use super::{crypto_algorithms::SigningKeyAlgorithm, DeviceId, KeyName};
+use super::{crypto_algorithms::SigningKeyAlgorithm, DeviceId, IdParseError, KeyName};
 
 /// A key algorithm and key name delimited by a colon.
 #[repr(transparent)]
@@ -66,6 +66,44 @@ pub type ServerSigningKeyId = SigningKeyId<KeyName>;
 /// Algorithm + key name for homeserver signing keys.
 pub type OwnedServerSigningKeyId = OwnedSigningKeyId<KeyName>;
 
+impl KeyId<SigningKeyAlgorithm, KeyName> {
+    /// Creates a new `OwnedServerSigningKeyId` from the given algorithm and key name strings.
+    ///
+    /// Returns an error if `algorithm` is empty or contains a colon, or if `key_name` is not a
+    /// valid key name.
+    pub fn from_algorithm_and_key_name(
+        algorithm: &str,
+        key_name: &str,
+    ) -> Result<OwnedServerSigningKeyId, IdParseError> {
+        if algorithm.is_empty() {
+            return Err(IdParseError::Empty);
+        }
+        if algorithm.contains(':') {
+            return Err(IdParseError::InvalidCharacters);
+        }
+
+        Self::parse(format!("{algorithm}:{key_name}"))
+    }
+
+    /// The ID of the room root key of [MSC3917], `ed25519:rrk`.
+    ///
+    /// [MSC3917]: https://github.com/matrix-org/matrix-spec-proposals/pull/3917
+    #[cfg(feature = "unstable-msc3917")]
+    pub fn rrk() -> OwnedServerSigningKeyId {
+        Self::from_parts(SigningKeyAlgorithm::Ed25519, <&KeyName>::from("rrk"))
+    }
+
+    /// Whether this is the ID of the room root key of [MSC3917], `ed25519:rrk`.
+    ///
+    /// Signatures with the room root key must use exactly this key ID.
+    ///
+    /// [MSC3917]: https://github.com/matrix-org/matrix-spec-proposals/pull/3917
+    #[cfg(feature = "unstable-msc3917")]
+    pub fn is_rrk(&self) -> bool {
+        self.algorithm() == SigningKeyAlgorithm::Ed25519 && self.key_name().as_str() == "rrk"
+    }
+}
+
 /// Algorithm + key name for device keys.
 pub type DeviceSigningKeyId = SigningKeyId<DeviceId>;
 
@@ -99,3 +137,41 @@ impl<A, K: ?Sized> Hash for KeyId<A, K> {
         self.as_str().hash(state);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ServerSigningKeyId;
+    use crate::{identifiers::IdParseError, SigningKeyAlgorithm};
+
+    #[test]
+    fn server_signing_key_id_from_algorithm_and_key_name() {
+        let key_id = ServerSigningKeyId::from_algorithm_and_key_name("ed25519", "abc_1").unwrap();
+        assert_eq!(key_id, "ed25519:abc_1");
+        assert_eq!(key_id.algorithm(), SigningKeyAlgorithm::Ed25519);
+        assert_eq!(key_id.key_name(), "abc_1");
+
+        assert_eq!(
+            ServerSigningKeyId::from_algorithm_and_key_name("", "abc_1").unwrap_err(),
+            IdParseError::Empty
+        );
+        assert_eq!(
+            ServerSigningKeyId::from_algorithm_and_key_name("ed:25519", "abc_1").unwrap_err(),
+            IdParseError::InvalidCharacters
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "unstable-msc3917")]
+    fn rrk() {
+        assert_eq!(ServerSigningKeyId::rrk(), "ed25519:rrk");
+        assert!(ServerSigningKeyId::rrk().is_rrk());
+
+        let key_id = ServerSigningKeyId::parse("ed25519:rrk").unwrap();
+        assert_eq!(key_id.algorithm(), SigningKeyAlgorithm::Ed25519);
+        assert_eq!(key_id.key_name(), "rrk");
+        assert!(key_id.is_rrk());
+
+        assert!(!ServerSigningKeyId::parse("ed25519:rrk2").unwrap().is_rrk());
+        assert!(!ServerSigningKeyId::parse("custom:rrk").unwrap().is_rrk());
+    }
+}
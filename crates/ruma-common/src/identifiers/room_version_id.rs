@@ -6,6 +6,7 @@ use ruma_macros::DisplayAsRefStr;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use super::IdParseError;
+use crate::room::JoinRuleKind;
 
 /// A Matrix [room version] ID.
 ///
@@ -88,6 +89,76 @@ impl RoomVersionId {
     pub fn as_bytes(&self) -> &[u8] {
         self.as_str().as_bytes()
     }
+
+    /// The format of the IDs of events in a room with this version.
+    ///
+    /// Custom room versions are assumed to use the format of the latest room version.
+    pub fn event_id_format(&self) -> EventIdFormat {
+        match self {
+            Self::V1 | Self::V2 => EventIdFormat::Original,
+            Self::V3 => EventIdFormat::Base64,
+            _ => EventIdFormat::UrlSafeBase64,
+        }
+    }
+
+    /// The generation of this room version, to compare the capabilities of room versions.
+    ///
+    /// Known room versions map to their number, so `version.capability_generation() >=
+    /// RoomVersionId::V11.capability_generation()` checks whether a room version is at least as
+    /// new as room version 11. Custom room versions map to `0`, because nothing can be assumed
+    /// about their capabilities.
+    pub fn capability_generation(&self) -> u8 {
+        match self {
+            Self::V1 => 1,
+            Self::V2 => 2,
+            Self::V3 => 3,
+            Self::V4 => 4,
+            Self::V5 => 5,
+            Self::V6 => 6,
+            Self::V7 => 7,
+            Self::V8 => 8,
+            Self::V9 => 9,
+            Self::V10 => 10,
+            Self::V11 => 11,
+            Self::_Custom(_) => 0,
+        }
+    }
+
+    /// Whether the given join rule can be used in a room with this version.
+    ///
+    /// `knock` is supported starting from room version 7, `restricted` from room version 8 and
+    /// `knock_restricted` from room version 10. Custom room versions are assumed to only support
+    /// the join rules available in all room versions, and custom join rules are never supported.
+    pub fn supports_join_rule(&self, rule: &JoinRuleKind) -> bool {
+        let generation = self.capability_generation();
+
+        match rule {
+            JoinRuleKind::Invite | JoinRuleKind::Private | JoinRuleKind::Public => true,
+            JoinRuleKind::Knock => generation >= 7,
+            JoinRuleKind::Restricted => generation >= 8,
+            JoinRuleKind::KnockRestricted => generation >= 10,
+            _ => false,
+        }
+    }
+}
+
+/// The format of [event IDs] in a room, which depends on its [`RoomVersionId`].
+///
+/// [event IDs]: https://spec.matrix.org/latest/appendices/#event-ids
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum EventIdFormat {
+    /// A pseudorandom localpart followed by the server name of the originating homeserver, as used
+    /// by room versions 1 and 2.
+    Original,
+
+    /// The reference hash of the event encoded with standard unpadded Base64, as used by room
+    /// version 3.
+    Base64,
+
+    /// The reference hash of the event encoded with URL-safe unpadded Base64, as used by room
+    /// versions 4 and later.
+    UrlSafeBase64,
 }
 
 impl From<RoomVersionId> for String {
@@ -263,8 +334,8 @@ impl AsRef<str> for CustomRoomVersion {
 
 #[cfg(test)]
 mod tests {
-    use super::RoomVersionId;
-    use crate::IdParseError;
+    use super::{EventIdFormat, RoomVersionId};
+    use crate::{room::JoinRuleKind, IdParseError};
 
     #[test]
     fn valid_version_1_room_version_id() {
@@ -390,7 +461,46 @@ mod tests {
         assert!(serde_json::from_str::<RoomVersionId>(r#""-""#).is_ok());
         assert_eq!(
             RoomVersionId::try_from("io_ruma_1").unwrap_err(),
-            IdParseError::InvalidCharacters
+            IdParseError::InvalidCharacter { character: '_', index: 2 }
+        );
+    }
+
+    #[test]
+    fn event_id_format() {
+        assert_eq!(RoomVersionId::V1.event_id_format(), EventIdFormat::Original);
+        assert_eq!(RoomVersionId::V2.event_id_format(), EventIdFormat::Original);
+        assert_eq!(RoomVersionId::V3.event_id_format(), EventIdFormat::Base64);
+        assert_eq!(RoomVersionId::V4.event_id_format(), EventIdFormat::UrlSafeBase64);
+        assert_eq!(RoomVersionId::V11.event_id_format(), EventIdFormat::UrlSafeBase64);
+        assert_eq!(
+            RoomVersionId::try_from("io.ruma.1").unwrap().event_id_format(),
+            EventIdFormat::UrlSafeBase64
         );
     }
+
+    #[test]
+    fn capability_generation() {
+        assert_eq!(RoomVersionId::V1.capability_generation(), 1);
+        assert_eq!(RoomVersionId::V11.capability_generation(), 11);
+        assert!(
+            RoomVersionId::V11.capability_generation() > RoomVersionId::V9.capability_generation()
+        );
+        assert_eq!(RoomVersionId::try_from("io.ruma.1").unwrap().capability_generation(), 0);
+    }
+
+    #[test]
+    fn supports_join_rule() {
+        assert!(RoomVersionId::V1.supports_join_rule(&JoinRuleKind::Public));
+        assert!(!RoomVersionId::V6.supports_join_rule(&JoinRuleKind::Knock));
+        assert!(RoomVersionId::V7.supports_join_rule(&JoinRuleKind::Knock));
+        assert!(!RoomVersionId::V7.supports_join_rule(&JoinRuleKind::Restricted));
+        assert!(RoomVersionId::V8.supports_join_rule(&JoinRuleKind::Restricted));
+        assert!(!RoomVersionId::V9.supports_join_rule(&JoinRuleKind::KnockRestricted));
+        assert!(RoomVersionId::V10.supports_join_rule(&JoinRuleKind::KnockRestricted));
+        assert!(!RoomVersionId::V11.supports_join_rule(&JoinRuleKind::from("org.example.rule")));
+
+        let custom = RoomVersionId::try_from("io.ruma.1").unwrap();
+        assert!(custom.supports_join_rule(&JoinRuleKind::Invite));
+        assert!(!custom.supports_join_rule(&JoinRuleKind::Restricted));
+    }
 }
@@ -55,3 +55,64 @@ pub type ServerSignatures = Signatures<OwnedServerName, OwnedKeyName>;
 
 /// Map of device signatures for an event, grouped by user.
 pub type DeviceSignatures = Signatures<OwnedUserId, OwnedDeviceId>;
+
+#[cfg(test)]
+mod tests {
+    use serde_json::to_string as to_json_string;
+
+    use super::{DeviceSignatures, ServerSignatures};
+    use crate::{
+        owned_device_id, owned_server_name, owned_user_id, KeyId, OwnedKeyName, SigningKeyAlgorithm,
+    };
+
+    #[test]
+    fn server_signatures_serialize_in_canonical_order() {
+        let mut signatures = ServerSignatures::new();
+        signatures.insert(
+            owned_server_name!("example.org"),
+            KeyId::from_parts(SigningKeyAlgorithm::Ed25519, &OwnedKeyName::from("b")),
+            "sig_b".to_owned(),
+        );
+        signatures.insert(
+            owned_server_name!("Example.org"),
+            KeyId::from_parts(SigningKeyAlgorithm::Ed25519, &OwnedKeyName::from("1")),
+            "sig_1".to_owned(),
+        );
+        signatures.insert(
+            owned_server_name!("example.org"),
+            KeyId::from_parts(SigningKeyAlgorithm::Ed25519, &OwnedKeyName::from("B")),
+            "sig_B".to_owned(),
+        );
+
+        // Keys must be sorted by their UTF-8 bytes, as canonical JSON requires.
+        assert_eq!(
+            to_json_string(&signatures).unwrap(),
+            r#"{"Example.org":{"ed25519:1":"sig_1"},"example.org":{"ed25519:B":"sig_B","ed25519:b":"sig_b"}}"#
+        );
+    }
+
+    #[test]
+    fn device_signatures_serialize_in_canonical_order() {
+        let mut signatures = DeviceSignatures::new();
+        signatures.insert(
+            owned_user_id!("@bob:example.org"),
+            KeyId::from_parts(SigningKeyAlgorithm::Ed25519, &owned_device_id!("DEVICE")),
+            "sig_bob".to_owned(),
+        );
+        signatures.insert(
+            owned_user_id!("@alice:example.org"),
+            KeyId::from_parts(SigningKeyAlgorithm::Ed25519, &owned_device_id!("ZDEVICE")),
+            "sig_alice_z".to_owned(),
+        );
+        signatures.insert(
+            owned_user_id!("@alice:example.org"),
+            KeyId::from_parts(SigningKeyAlgorithm::Ed25519, &owned_device_id!("ADEVICE")),
+            "sig_alice_a".to_owned(),
+        );
+
+        assert_eq!(
+            to_json_string(&signatures).unwrap(),
+            r#"{"@alice:example.org":{"ed25519:ADEVICE":"sig_alice_a","ed25519:ZDEVICE":"sig_alice_z"},"@bob:example.org":{"ed25519:DEVICE":"sig_bob"}}"#
+        );
+    }
+}
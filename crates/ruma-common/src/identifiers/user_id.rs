@@ -307,7 +307,7 @@ mod tests {
     fn invalid_characters_in_user_id_localpart() {
         assert_eq!(
             <&UserId>::try_from("@te\nst:example.com").unwrap_err(),
-            IdParseError::InvalidCharacters
+            IdParseError::InvalidCharacter { character: '\n', index: 3 }
         );
     }
 
@@ -29,6 +29,7 @@ mod percent_encode;
 pub mod power_levels;
 pub mod presence;
 pub mod push;
+pub mod redaction;
 pub mod room;
 pub mod serde;
 pub mod space;
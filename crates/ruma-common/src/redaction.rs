@@ -0,0 +1,599 @@
+//! Rules to redact the content of events.
+//!
+//! These rules are used by the [redaction algorithm] of the spec.
+//!
+//! [redaction algorithm]: https://spec.matrix.org/latest/rooms/v11/#redactions
+
+use std::{fmt, mem};
+
+use serde_json::Value as JsonValue;
+
+#[cfg(feature = "canonical-json")]
+use crate::canonical_json::{CanonicalJsonObject, CanonicalJsonValue};
+use crate::{serde::JsonObject, RoomVersionId};
+
+/// Errors that can happen in redaction.
+#[derive(Debug)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum RedactionError {
+    /// The field `field` is not of the correct type `of_type` ([`JsonType`]).
+    #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+    NotOfType {
+        /// The field name.
+        field: String,
+        /// The expected JSON type.
+        of_type: JsonType,
+    },
+
+    /// The given required field is missing from a JSON object.
+    JsonFieldMissingFromObject(String),
+}
+
+impl fmt::Display for RedactionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RedactionError::NotOfType { field, of_type } => {
+                write!(f, "Value in {field:?} must be a JSON {of_type:?}")
+            }
+            RedactionError::JsonFieldMissingFromObject(field) => {
+                write!(f, "JSON object must contain the field {field:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RedactionError {}
+
+impl RedactionError {
+    pub(crate) fn not_of_type(target: &str, of_type: JsonType) -> Self {
+        Self::NotOfType { field: target.to_owned(), of_type }
+    }
+
+    #[cfg(feature = "canonical-json")]
+    pub(crate) fn field_missing_from_object(target: &str) -> Self {
+        Self::JsonFieldMissingFromObject(target.to_owned())
+    }
+}
+
+/// A JSON type enum for [`RedactionError`] variants.
+#[derive(Debug)]
+#[allow(clippy::exhaustive_enums)]
+pub enum JsonType {
+    /// A JSON Object.
+    Object,
+
+    /// A JSON String.
+    String,
+
+    /// A JSON Integer.
+    Integer,
+
+    /// A JSON Array.
+    Array,
+
+    /// A JSON Boolean.
+    Boolean,
+
+    /// JSON Null.
+    Null,
+}
+
+/// List of keys to preserve on an object during redaction.
+///
+/// Use [`allowed_content_keys_for()`] to get the keys to preserve in the content of an event.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct AllowedKeys {
+    /// Whether all keys are preserved.
+    all: bool,
+
+    /// The keys to preserve on this object.
+    keys: &'static [&'static str],
+
+    /// Keys to preserve on nested objects.
+    ///
+    /// A list of `(nested_object_key, nested_allowed_keys)`.
+    nested: &'static [(&'static str, &'static AllowedKeys)],
+}
+
+impl AllowedKeys {
+    /// All keys are preserved.
+    const ALL: Self = Self { all: true, keys: &[], nested: &[] };
+
+    /// No keys are preserved.
+    const NONE: Self = Self { all: false, keys: &[], nested: &[] };
+
+    /// Creates an new `AllowedKeys` with the given keys at this level.
+    const fn some(keys: &'static [&'static str]) -> Self {
+        Self { all: false, keys, nested: &[] }
+    }
+
+    /// Creates an new `AllowedKeys` with the given keys and nested keys.
+    const fn some_nested(
+        keys: &'static [&'static str],
+        nested: &'static [(&'static str, &'static AllowedKeys)],
+    ) -> Self {
+        Self { all: false, keys, nested }
+    }
+
+    /// Whether all the keys of the object are preserved.
+    ///
+    /// If this is `true`, [`keys()`](Self::keys) and [`nested()`](Self::nested) are empty.
+    pub fn is_all(&self) -> bool {
+        self.all
+    }
+
+    /// The keys whose values are preserved as-is.
+    pub fn keys(&self) -> &'static [&'static str] {
+        self.keys
+    }
+
+    /// The keys of nested objects of which only some keys are preserved.
+    ///
+    /// This is a list of `(nested_object_key, nested_allowed_keys)`.
+    pub fn nested(&self) -> &'static [(&'static str, &'static AllowedKeys)] {
+        self.nested
+    }
+
+    /// Removes the keys of the given JSON object that are not preserved.
+    ///
+    /// Nested objects that are empty once their own keys have been redacted are removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value of a nested object with preserved keys is not a JSON object.
+    pub fn retain_in_json(&self, object: &mut JsonObject) -> Result<(), RedactionError> {
+        retain_keys(object, self)
+    }
+}
+
+/// A JSON object that can be redacted with [`AllowedKeys`].
+pub(crate) trait RedactableObject: Default {
+    /// The type of the values of the object.
+    type Value;
+
+    /// Removes the entry with the given key.
+    fn remove_entry(&mut self, key: &str) -> Option<(String, Self::Value)>;
+
+    /// Inserts a value with the given key.
+    fn insert(&mut self, key: String, value: Self::Value);
+
+    /// Whether this object is empty.
+    fn is_empty(&self) -> bool;
+
+    /// Accesses the given value as an object, if it is one.
+    fn value_as_object_mut(value: &mut Self::Value) -> Option<&mut Self>;
+}
+
+impl RedactableObject for JsonObject {
+    type Value = JsonValue;
+
+    fn remove_entry(&mut self, key: &str) -> Option<(String, Self::Value)> {
+        serde_json::Map::remove_entry(self, key)
+    }
+
+    fn insert(&mut self, key: String, value: Self::Value) {
+        serde_json::Map::insert(self, key, value);
+    }
+
+    fn is_empty(&self) -> bool {
+        serde_json::Map::is_empty(self)
+    }
+
+    fn value_as_object_mut(value: &mut Self::Value) -> Option<&mut Self> {
+        value.as_object_mut()
+    }
+}
+
+#[cfg(feature = "canonical-json")]
+impl RedactableObject for CanonicalJsonObject {
+    type Value = CanonicalJsonValue;
+
+    fn remove_entry(&mut self, key: &str) -> Option<(String, Self::Value)> {
+        CanonicalJsonObject::remove_entry(self, key)
+    }
+
+    fn insert(&mut self, key: String, value: Self::Value) {
+        CanonicalJsonObject::insert(self, key, value);
+    }
+
+    fn is_empty(&self) -> bool {
+        CanonicalJsonObject::is_empty(self)
+    }
+
+    fn value_as_object_mut(value: &mut Self::Value) -> Option<&mut Self> {
+        match value {
+            CanonicalJsonValue::Object(object) => Some(object),
+            _ => None,
+        }
+    }
+}
+
+/// Removes the keys of the given object that are not preserved according to `allowed_keys`.
+pub(crate) fn retain_keys<O: RedactableObject>(
+    object: &mut O,
+    allowed_keys: &AllowedKeys,
+) -> Result<(), RedactionError> {
+    if allowed_keys.all {
+        return Ok(());
+    }
+
+    let mut old_object = mem::take(object);
+
+    for &(nested_key, nested_allowed_keys) in allowed_keys.nested {
+        if let Some((key, mut nested_object_value)) = old_object.remove_entry(nested_key) {
+            let nested_object = O::value_as_object_mut(&mut nested_object_value)
+                .ok_or_else(|| RedactionError::not_of_type(nested_key, JsonType::Object))?;
+
+            retain_keys(nested_object, nested_allowed_keys)?;
+
+            // If the object is empty, it means none of the nested fields were found so we
+            // don't want to keep the object.
+            if !nested_object.is_empty() {
+                object.insert(key, nested_object_value);
+            }
+        }
+    }
+
+    for &key in allowed_keys.keys {
+        if let Some((key, value)) = old_object.remove_entry(key) {
+            object.insert(key, value);
+        }
+    }
+
+    Ok(())
+}
+
+/// Allowed keys in `m.room.member`'s content according to room version 1.
+static ROOM_MEMBER_V1: AllowedKeys = AllowedKeys::some(&["membership"]);
+/// Allowed keys in `m.room.member`'s content according to room version 9.
+static ROOM_MEMBER_V9: AllowedKeys =
+    AllowedKeys::some(&["membership", "join_authorised_via_users_server"]);
+/// Allowed keys in `m.room.member`'s content according to room version 11.
+static ROOM_MEMBER_V11: AllowedKeys = AllowedKeys::some_nested(
+    &["membership", "join_authorised_via_users_server"],
+    &[("third_party_invite", &ROOM_MEMBER_THIRD_PARTY_INVITE_V11)],
+);
+/// Allowed keys in the `third_party_invite` field of `m.room.member`'s content according to room
+/// version 11.
+static ROOM_MEMBER_THIRD_PARTY_INVITE_V11: AllowedKeys = AllowedKeys::some(&["signed"]);
+
+/// Allowed keys in `m.room.create`'s content according to room version 1.
+static ROOM_CREATE_V1: AllowedKeys = AllowedKeys::some(&["creator"]);
+
+/// Allowed keys in `m.room.join_rules`'s content according to room version 1.
+static ROOM_JOIN_RULES_V1: AllowedKeys = AllowedKeys::some(&["join_rule"]);
+/// Allowed keys in `m.room.join_rules`'s content according to room version 8.
+static ROOM_JOIN_RULES_V8: AllowedKeys = AllowedKeys::some(&["join_rule", "allow"]);
+
+/// Allowed keys in `m.room.power_levels`'s content according to room version 1.
+static ROOM_POWER_LEVELS_V1: AllowedKeys = AllowedKeys::some(&[
+    "ban",
+    "events",
+    "events_default",
+    "kick",
+    "redact",
+    "state_default",
+    "users",
+    "users_default",
+]);
+/// Allowed keys in `m.room.power_levels`'s content according to room version 11.
+static ROOM_POWER_LEVELS_V11: AllowedKeys = AllowedKeys::some(&[
+    "ban",
+    "events",
+    "events_default",
+    "invite",
+    "kick",
+    "redact",
+    "state_default",
+    "users",
+    "users_default",
+]);
+
+/// Allowed keys in `m.room.aliases`'s content according to room version 1.
+static ROOM_ALIASES_V1: AllowedKeys = AllowedKeys::some(&["aliases"]);
+
+/// Allowed keys in `m.room.server_acl`'s content according to MSC2870.
+#[cfg(feature = "unstable-msc2870")]
+static ROOM_SERVER_ACL_MSC2870: AllowedKeys =
+    AllowedKeys::some(&["allow", "deny", "allow_ip_literals"]);
+
+/// Allowed keys in `m.room.history_visibility`'s content according to room version 1.
+static ROOM_HISTORY_VISIBILITY_V1: AllowedKeys = AllowedKeys::some(&["history_visibility"]);
+
+/// Allowed keys in `m.room.redaction`'s content according to room version 11.
+static ROOM_REDACTION_V11: AllowedKeys = AllowedKeys::some(&["redacts"]);
+
+/// The keys of the content of an event with the given type that are preserved during redaction in
+/// a room with the given version.
+pub fn allowed_content_keys_for(event_type: &str, version: &RoomVersionId) -> &'static AllowedKeys {
+    match event_type {
+        "m.room.member" => match version {
+            RoomVersionId::V1
+            | RoomVersionId::V2
+            | RoomVersionId::V3
+            | RoomVersionId::V4
+            | RoomVersionId::V5
+            | RoomVersionId::V6
+            | RoomVersionId::V7
+            | RoomVersionId::V8 => &ROOM_MEMBER_V1,
+            RoomVersionId::V9 | RoomVersionId::V10 => &ROOM_MEMBER_V9,
+            _ => &ROOM_MEMBER_V11,
+        },
+        "m.room.create" => match version {
+            RoomVersionId::V1
+            | RoomVersionId::V2
+            | RoomVersionId::V3
+            | RoomVersionId::V4
+            | RoomVersionId::V5
+            | RoomVersionId::V6
+            | RoomVersionId::V7
+            | RoomVersionId::V8
+            | RoomVersionId::V9
+            | RoomVersionId::V10 => &ROOM_CREATE_V1,
+            _ => &AllowedKeys::ALL,
+        },
+        "m.room.join_rules" => match version {
+            RoomVersionId::V1
+            | RoomVersionId::V2
+            | RoomVersionId::V3
+            | RoomVersionId::V4
+            | RoomVersionId::V5
+            | RoomVersionId::V6
+            | RoomVersionId::V7 => &ROOM_JOIN_RULES_V1,
+            _ => &ROOM_JOIN_RULES_V8,
+        },
+        "m.room.power_levels" => match version {
+            RoomVersionId::V1
+            | RoomVersionId::V2
+            | RoomVersionId::V3
+            | RoomVersionId::V4
+            | RoomVersionId::V5
+            | RoomVersionId::V6
+            | RoomVersionId::V7
+            | RoomVersionId::V8
+            | RoomVersionId::V9
+            | RoomVersionId::V10 => &ROOM_POWER_LEVELS_V1,
+            _ => &ROOM_POWER_LEVELS_V11,
+        },
+        "m.room.aliases" => match version {
+            RoomVersionId::V1
+            | RoomVersionId::V2
+            | RoomVersionId::V3
+            | RoomVersionId::V4
+            | RoomVersionId::V5 => &ROOM_ALIASES_V1,
+            // All other room versions, including custom ones, are treated by version 6 rules.
+            // TODO: Should we return an error for unknown versions instead?
+            _ => &AllowedKeys::NONE,
+        },
+        #[cfg(feature = "unstable-msc2870")]
+        "m.room.server_acl" if version.as_str() == "org.matrix.msc2870" => &ROOM_SERVER_ACL_MSC2870,
+        "m.room.history_visibility" => &ROOM_HISTORY_VISIBILITY_V1,
+        "m.room.redaction" => match version {
+            RoomVersionId::V1
+            | RoomVersionId::V2
+            | RoomVersionId::V3
+            | RoomVersionId::V4
+            | RoomVersionId::V5
+            | RoomVersionId::V6
+            | RoomVersionId::V7
+            | RoomVersionId::V8
+            | RoomVersionId::V9
+            | RoomVersionId::V10 => &AllowedKeys::NONE,
+            _ => &ROOM_REDACTION_V11,
+        },
+        _ => &AllowedKeys::NONE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches2::assert_matches;
+    use serde_json::{json, Value as JsonValue};
+
+    use super::{allowed_content_keys_for, JsonType, RedactionError};
+    use crate::RoomVersionId;
+
+    fn redact_content(event_type: &str, version: &RoomVersionId, content: JsonValue) -> JsonValue {
+        let JsonValue::Object(mut object) = content else { panic!("content should be an object") };
+        allowed_content_keys_for(event_type, version).retain_in_json(&mut object).unwrap();
+        JsonValue::Object(object)
+    }
+
+    #[test]
+    fn room_member() {
+        let content = json!({
+            "avatar_url": "mxc://localhost/abcdef",
+            "displayname": "Alice",
+            "join_authorised_via_users_server": "@bob:localhost",
+            "membership": "invite",
+            "third_party_invite": {
+                "display_name": "alice",
+                "signed": {
+                    "mxid": "@alice:localhost",
+                    "signatures": {},
+                    "token": "abc123",
+                },
+            },
+        });
+
+        assert_eq!(
+            redact_content("m.room.member", &RoomVersionId::V8, content.clone()),
+            json!({ "membership": "invite" })
+        );
+        assert_eq!(
+            redact_content("m.room.member", &RoomVersionId::V9, content.clone()),
+            json!({
+                "join_authorised_via_users_server": "@bob:localhost",
+                "membership": "invite",
+            })
+        );
+        assert_eq!(
+            redact_content("m.room.member", &RoomVersionId::V11, content),
+            json!({
+                "join_authorised_via_users_server": "@bob:localhost",
+                "membership": "invite",
+                "third_party_invite": {
+                    "signed": {
+                        "mxid": "@alice:localhost",
+                        "signatures": {},
+                        "token": "abc123",
+                    },
+                },
+            })
+        );
+
+        // Nested objects without any preserved key are removed.
+        assert_eq!(
+            redact_content(
+                "m.room.member",
+                &RoomVersionId::V11,
+                json!({
+                    "membership": "invite",
+                    "third_party_invite": { "display_name": "alice" },
+                })
+            ),
+            json!({ "membership": "invite" })
+        );
+    }
+
+    #[test]
+    fn nested_value_not_object() {
+        let JsonValue::Object(mut object) = json!({
+            "membership": "invite",
+            "third_party_invite": "not an object",
+        }) else {
+            unreachable!()
+        };
+
+        let err = allowed_content_keys_for("m.room.member", &RoomVersionId::V11)
+            .retain_in_json(&mut object)
+            .unwrap_err();
+        assert_matches!(err, RedactionError::NotOfType { field, of_type: JsonType::Object });
+        assert_eq!(field, "third_party_invite");
+    }
+
+    #[test]
+    fn room_create() {
+        let content = json!({
+            "creator": "@alice:localhost",
+            "m.federate": false,
+            "room_version": "11",
+            "type": "m.space",
+        });
+
+        assert_eq!(
+            redact_content("m.room.create", &RoomVersionId::V10, content.clone()),
+            json!({ "creator": "@alice:localhost" })
+        );
+        assert_eq!(redact_content("m.room.create", &RoomVersionId::V11, content.clone()), content);
+    }
+
+    #[test]
+    fn room_join_rules() {
+        let content = json!({
+            "join_rule": "restricted",
+            "allow": [{ "type": "m.room_membership", "room_id": "!space:localhost" }],
+            "foo": "bar",
+        });
+
+        assert_eq!(
+            redact_content("m.room.join_rules", &RoomVersionId::V7, content.clone()),
+            json!({ "join_rule": "restricted" })
+        );
+        assert_eq!(
+            redact_content("m.room.join_rules", &RoomVersionId::V8, content),
+            json!({
+                "join_rule": "restricted",
+                "allow": [{ "type": "m.room_membership", "room_id": "!space:localhost" }],
+            })
+        );
+    }
+
+    #[test]
+    fn room_power_levels() {
+        let content = json!({
+            "ban": 50,
+            "events": { "m.room.name": 50 },
+            "events_default": 0,
+            "invite": 0,
+            "kick": 50,
+            "notifications": { "room": 50 },
+            "redact": 50,
+            "state_default": 50,
+            "users": { "@alice:localhost": 100 },
+            "users_default": 0,
+        });
+
+        assert_eq!(
+            redact_content("m.room.power_levels", &RoomVersionId::V10, content.clone()),
+            json!({
+                "ban": 50,
+                "events": { "m.room.name": 50 },
+                "events_default": 0,
+                "kick": 50,
+                "redact": 50,
+                "state_default": 50,
+                "users": { "@alice:localhost": 100 },
+                "users_default": 0,
+            })
+        );
+        assert_eq!(
+            redact_content("m.room.power_levels", &RoomVersionId::V11, content),
+            json!({
+                "ban": 50,
+                "events": { "m.room.name": 50 },
+                "events_default": 0,
+                "invite": 0,
+                "kick": 50,
+                "redact": 50,
+                "state_default": 50,
+                "users": { "@alice:localhost": 100 },
+                "users_default": 0,
+            })
+        );
+    }
+
+    #[test]
+    fn room_aliases() {
+        let content = json!({ "aliases": ["#somewhere:localhost"] });
+
+        assert_eq!(redact_content("m.room.aliases", &RoomVersionId::V5, content.clone()), content);
+        assert_eq!(redact_content("m.room.aliases", &RoomVersionId::V6, content), json!({}));
+    }
+
+    #[test]
+    fn room_history_visibility() {
+        let content = json!({ "history_visibility": "shared", "foo": "bar" });
+
+        assert_eq!(
+            redact_content("m.room.history_visibility", &RoomVersionId::V1, content.clone()),
+            json!({ "history_visibility": "shared" })
+        );
+        assert_eq!(
+            redact_content("m.room.history_visibility", &RoomVersionId::V11, content),
+            json!({ "history_visibility": "shared" })
+        );
+    }
+
+    #[test]
+    fn room_redaction() {
+        let content = json!({ "redacts": "$abcdef", "reason": "Spam" });
+
+        assert_eq!(
+            redact_content("m.room.redaction", &RoomVersionId::V10, content.clone()),
+            json!({})
+        );
+        assert_eq!(
+            redact_content("m.room.redaction", &RoomVersionId::V11, content),
+            json!({ "redacts": "$abcdef" })
+        );
+    }
+
+    #[test]
+    fn other_event_type() {
+        let content = json!({ "body": "Room upgraded", "replacement_room": "!new:localhost" });
+
+        assert_eq!(redact_content("m.room.tombstone", &RoomVersionId::V11, content), json!({}));
+    }
+}
@@ -26,9 +26,9 @@ pub use self::{
     cow::deserialize_cow_str,
     raw::Raw,
     strings::{
-        btreemap_deserialize_v1_powerlevel_values, deserialize_as_f64_or_string,
-        deserialize_as_optional_f64_or_string, deserialize_v1_powerlevel, empty_string_as_none,
-        none_as_empty_string,
+        btreemap_deserialize_v1_powerlevel_values, deserialize_as_bool_or_string,
+        deserialize_as_f64_or_string, deserialize_as_optional_f64_or_string,
+        deserialize_v1_powerlevel, empty_string_as_none, none_as_empty_string,
     },
 };
 
@@ -73,6 +73,22 @@ where
     serde_json::from_str(val.get()).map_err(E::custom)
 }
 
+/// Like [`from_raw_json_value`], but adds the given context to the error message.
+///
+/// The context is meant to be the type of the content that is deserialized, for example
+/// `m.image`, which results in an error message like `failed to parse m.image content: …`.
+pub fn from_raw_json_value_with_context<'a, T, E>(
+    val: &'a RawJsonValue,
+    context: &str,
+) -> Result<T, E>
+where
+    T: Deserialize<'a>,
+    E: de::Error,
+{
+    serde_json::from_str(val.get())
+        .map_err(|error| E::custom(format_args!("failed to parse {context} content: {error}")))
+}
+
 pub use ruma_macros::{
     AsRefStr, DebugAsRefStr, DeserializeFromCowStr, DisplayAsRefStr, FromString, OrdAsRefStr,
     PartialEqAsRefStr, PartialOrdAsRefStr, SerializeAsRefStr, StringEnum, _FakeDeriveSerde,
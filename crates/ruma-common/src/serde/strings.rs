@@ -91,6 +91,37 @@ where
 #[derive(Deserialize)]
 struct F64OrStringWrapper(#[serde(deserialize_with = "deserialize_as_f64_or_string")] f64);
 
+/// Take either a boolean or a string and deserialize to a boolean.
+///
+/// Only the strings `"true"` and `"false"` are accepted.
+///
+/// To be used like this:
+/// `#[serde(deserialize_with = "deserialize_as_bool_or_string")]`
+pub fn deserialize_as_bool_or_string<'de, D>(de: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct BoolOrStringVisitor;
+
+    impl<'de> Visitor<'de> for BoolOrStringVisitor {
+        type Value = bool;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a boolean or a string")
+        }
+
+        fn visit_bool<E: de::Error>(self, v: bool) -> Result<Self::Value, E> {
+            Ok(v)
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            v.parse().map_err(E::custom)
+        }
+    }
+
+    de.deserialize_any(BoolOrStringVisitor)
+}
+
 /// Deserializes an `Option<f64>` as encoded as a f64 or a string.
 pub fn deserialize_as_optional_f64_or_string<'de, D>(
     deserializer: D,
@@ -234,7 +265,7 @@ mod tests {
     use js_int::{int, Int};
     use serde::Deserialize;
 
-    use super::deserialize_v1_powerlevel;
+    use super::{deserialize_as_bool_or_string, deserialize_v1_powerlevel};
 
     #[derive(Debug, Deserialize)]
     struct Test {
@@ -264,4 +295,23 @@ mod tests {
         .unwrap();
         assert_eq!(test.num, int!(-1000));
     }
+
+    #[derive(Debug, Deserialize)]
+    struct BoolTest {
+        #[serde(deserialize_with = "deserialize_as_bool_or_string")]
+        flag: bool,
+    }
+
+    #[test]
+    fn bool_or_string() {
+        let test = serde_json::from_value::<BoolTest>(serde_json::json!({ "flag": true })).unwrap();
+        assert!(test.flag);
+
+        let test =
+            serde_json::from_value::<BoolTest>(serde_json::json!({ "flag": "false" })).unwrap();
+        assert!(!test.flag);
+
+        serde_json::from_value::<BoolTest>(serde_json::json!({ "flag": "no" })).unwrap_err();
+        serde_json::from_value::<BoolTest>(serde_json::json!({ "flag": 1 })).unwrap_err();
+    }
 }
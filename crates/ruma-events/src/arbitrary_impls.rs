@@ -0,0 +1,173 @@
+//! Implementations of [`Arbitrary`] for some event content types.
+//!
+//! The generated values only contain valid identifiers, so they can be serialized and deserialized
+//! back to the same value.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+use ruma_common::{
+    room::RoomType, OwnedEventId, OwnedRoomId, OwnedServerName, OwnedUserId, RoomVersionId,
+};
+
+use crate::{
+    room::{
+        create::{PreviousRoom, RoomCreateEventContent},
+        message::RoomMessageEventContent,
+        tombstone::RoomTombstoneEventContent,
+    },
+    space::child::SpaceChildEventContent,
+    Mentions,
+};
+
+/// The characters used in the generated identifiers.
+const ID_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+
+fn arbitrary_label(u: &mut Unstructured<'_>) -> Result<String> {
+    let len = u.int_in_range(1..=8)?;
+    (0..len).map(|_| u.choose(ID_CHARS).map(|&c| char::from(c))).collect()
+}
+
+fn arbitrary_option<T>(
+    u: &mut Unstructured<'_>,
+    f: impl FnOnce(&mut Unstructured<'_>) -> Result<T>,
+) -> Result<Option<T>> {
+    Ok(if u.arbitrary()? { Some(f(u)?) } else { None })
+}
+
+fn arbitrary_vec<T>(
+    u: &mut Unstructured<'_>,
+    mut f: impl FnMut(&mut Unstructured<'_>) -> Result<T>,
+) -> Result<Vec<T>> {
+    let len = u.int_in_range(0..=3)?;
+    (0..len).map(|_| f(u)).collect()
+}
+
+fn arbitrary_server_name(u: &mut Unstructured<'_>) -> Result<OwnedServerName> {
+    let server_name = format!("{}.example.org", arbitrary_label(u)?);
+    Ok(server_name.try_into().expect("generated server name should be valid"))
+}
+
+fn arbitrary_user_id(u: &mut Unstructured<'_>) -> Result<OwnedUserId> {
+    let user_id = format!("@{}:{}", arbitrary_label(u)?, arbitrary_server_name(u)?);
+    Ok(user_id.try_into().expect("generated user ID should be valid"))
+}
+
+fn arbitrary_room_id(u: &mut Unstructured<'_>) -> Result<OwnedRoomId> {
+    let room_id = format!("!{}:{}", arbitrary_label(u)?, arbitrary_server_name(u)?);
+    Ok(room_id.try_into().expect("generated room ID should be valid"))
+}
+
+fn arbitrary_event_id(u: &mut Unstructured<'_>) -> Result<OwnedEventId> {
+    let event_id = format!("${}", arbitrary_label(u)?);
+    Ok(event_id.try_into().expect("generated event ID should be valid"))
+}
+
+impl<'a> Arbitrary<'a> for RoomCreateEventContent {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let room_version = u
+            .choose(&[
+                RoomVersionId::V1,
+                RoomVersionId::V2,
+                RoomVersionId::V3,
+                RoomVersionId::V4,
+                RoomVersionId::V5,
+                RoomVersionId::V6,
+                RoomVersionId::V7,
+                RoomVersionId::V8,
+                RoomVersionId::V9,
+                RoomVersionId::V10,
+                RoomVersionId::V11,
+            ])?
+            .clone();
+
+        #[allow(deprecated)]
+        Ok(Self {
+            creator: arbitrary_option(u, arbitrary_user_id)?,
+            federate: u.arbitrary()?,
+            room_version,
+            predecessor: arbitrary_option(u, |u| {
+                Ok(PreviousRoom::new(arbitrary_room_id(u)?, arbitrary_event_id(u)?))
+            })?,
+            room_type: arbitrary_option(u, |_| Ok(RoomType::Space))?,
+            #[cfg(feature = "unstable-msc4289")]
+            additional_creators: arbitrary_vec(u, arbitrary_user_id)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for SpaceChildEventContent {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut content = Self::new(arbitrary_vec(u, arbitrary_server_name)?);
+        content.order = arbitrary_option(u, |u| {
+            Ok(arbitrary_label(u)?.try_into().expect("generated order should be valid"))
+        })?;
+        content.suggested = u.arbitrary()?;
+
+        Ok(content)
+    }
+}
+
+impl<'a> Arbitrary<'a> for RoomTombstoneEventContent {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self::new(u.arbitrary()?, arbitrary_room_id(u)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for RoomMessageEventContent {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let body: String = u.arbitrary()?;
+        let html_body: Option<String> = u.arbitrary()?;
+
+        let mut content = match (u.int_in_range(0..=2)?, html_body) {
+            (0, None) => Self::text_plain(body),
+            (0, Some(html_body)) => Self::text_html(body, html_body),
+            (1, None) => Self::notice_plain(body),
+            (1, Some(html_body)) => Self::notice_html(body, html_body),
+            (_, None) => Self::emote_plain(body),
+            (_, Some(html_body)) => Self::emote_html(body, html_body),
+        };
+        content.mentions = arbitrary_option(u, |u| {
+            Ok(Mentions::with_user_ids(arbitrary_vec(u, arbitrary_user_id)?))
+        })?;
+
+        Ok(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arbitrary::{Arbitrary, Unstructured};
+    use serde::{de::DeserializeOwned, Serialize};
+    use serde_json::{from_value as from_json_value, to_value as to_json_value};
+
+    use crate::{
+        room::{
+            create::RoomCreateEventContent, message::RoomMessageEventContent,
+            tombstone::RoomTombstoneEventContent,
+        },
+        space::child::SpaceChildEventContent,
+    };
+
+    fn assert_roundtrip<T>()
+    where
+        T: for<'a> Arbitrary<'a> + Serialize + DeserializeOwned,
+    {
+        for seed in 0..64_u32 {
+            let data: Vec<u8> = (0..256_u32)
+                .map(|i| (seed.wrapping_mul(31).wrapping_add(i.wrapping_mul(17)) % 256) as u8)
+                .collect();
+            let value = T::arbitrary(&mut Unstructured::new(&data)).unwrap();
+
+            let json = to_json_value(&value).unwrap();
+            let deserialized: T = from_json_value(json.clone()).unwrap();
+            assert_eq!(to_json_value(&deserialized).unwrap(), json);
+        }
+    }
+
+    #[test]
+    fn arbitrary_roundtrip() {
+        assert_roundtrip::<RoomCreateEventContent>();
+        assert_roundtrip::<SpaceChildEventContent>();
+        assert_roundtrip::<RoomTombstoneEventContent>();
+        assert_roundtrip::<RoomMessageEventContent>();
+    }
+}
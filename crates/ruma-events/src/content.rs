@@ -1,12 +1,15 @@
 use std::fmt;
 
-use ruma_common::serde::{CanBeEmpty, Raw};
+use ruma_common::{
+    serde::{CanBeEmpty, Raw},
+    OwnedUserId,
+};
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::{from_str as from_json_str, value::RawValue as RawJsonValue};
 
 use super::{
-    EphemeralRoomEventType, GlobalAccountDataEventType, MessageLikeEventType,
-    RoomAccountDataEventType, StateEventType, ToDeviceEventType,
+    AnyStrippedStateEvent, EphemeralRoomEventType, GlobalAccountDataEventType,
+    MessageLikeEventType, RoomAccountDataEventType, StateEventType, ToDeviceEventType,
 };
 
 /// The base trait that all event content types implement.
@@ -66,6 +69,36 @@ pub trait RedactedMessageLikeEventContent: EventContent<EventType = MessageLikeE
 pub trait StateEventContent: EventContent<EventType = StateEventType> {
     /// The type of the event's `state_key` field.
     type StateKey: AsRef<str> + Clone + fmt::Debug + DeserializeOwned + Serialize;
+
+    /// Converts this content into a stripped state event sent by the given user with the given
+    /// state key.
+    ///
+    /// Since none of the content types in Ruma ever return an error in serialization, this will
+    /// never panic with a content type from Ruma. However, if you use a custom content type with a
+    /// `Serialize` implementation that can error (for example because it contains an `enum` with
+    /// one or more variants that use the `#[serde(skip)]` attribute), this method can panic.
+    fn into_stripped(
+        self,
+        sender: OwnedUserId,
+        state_key: Self::StateKey,
+    ) -> Raw<AnyStrippedStateEvent> {
+        #[derive(Serialize)]
+        struct StrippedStateEventSerHelper<C, K> {
+            #[serde(rename = "type")]
+            event_type: StateEventType,
+            content: C,
+            sender: OwnedUserId,
+            state_key: K,
+        }
+
+        let event = StrippedStateEventSerHelper {
+            event_type: self.event_type(),
+            content: self,
+            sender,
+            state_key,
+        };
+        Raw::new(&event).unwrap().cast()
+    }
 }
 
 /// Content of a non-redacted state event with a corresponding possibly-redacted type.
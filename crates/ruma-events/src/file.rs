@@ -5,7 +5,10 @@
 use std::collections::BTreeMap;
 
 use js_int::UInt;
-use ruma_common::{serde::Base64, OwnedMxcUri};
+use ruma_common::{
+    serde::{Base64, JsonObject},
+    OwnedMxcUri,
+};
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
 
@@ -146,12 +149,25 @@ pub struct FileContentBlock {
     /// Required if the file is encrypted.
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
     pub encryption_info: Option<Box<EncryptedContent>>,
+
+    /// Additional fields that are not defined in the MSC, like vendor-specific metadata.
+    ///
+    /// They are kept when deserializing and serializing the content, so they can round-trip.
+    #[serde(flatten, skip_serializing_if = "JsonObject::is_empty")]
+    pub extra: JsonObject,
 }
 
 impl FileContentBlock {
     /// Creates a new non-encrypted `FileContentBlock` with the given url and name.
     pub fn plain(url: OwnedMxcUri, name: String) -> Self {
-        Self { url, name, mimetype: None, size: None, encryption_info: None }
+        Self {
+            url,
+            name,
+            mimetype: None,
+            size: None,
+            encryption_info: None,
+            extra: JsonObject::new(),
+        }
     }
 
     /// Creates a new encrypted `FileContentBlock` with the given url, name and encryption info.
@@ -162,6 +178,7 @@ impl FileContentBlock {
             mimetype: None,
             size: None,
             encryption_info: Some(Box::new(encryption_info)),
+            extra: JsonObject::new(),
         }
     }
 
@@ -108,6 +108,11 @@ impl ImageEventContent {
             relates_to: None,
         }
     }
+
+    /// The plain text representation of the caption of this image, if any.
+    pub fn caption_text(&self) -> Option<&str> {
+        self.caption.as_ref()?.text.find_plain()
+    }
 }
 
 /// A block for details of image content.
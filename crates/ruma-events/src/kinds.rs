@@ -709,6 +709,13 @@ impl_possibly_redacted_event!(
             }
         }
 
+        /// Returns this event's `state_key` field as a string, as it appears on the wire.
+        ///
+        /// This is useful for generic code that doesn't care about the type of the state key.
+        pub fn state_key_str(&self) -> &str {
+            self.state_key().as_ref()
+        }
+
         /// Get the inner `OriginalStateEvent` if this is an unredacted event.
         pub fn as_original(&self) -> Option<&OriginalStateEvent<C>> {
             as_variant!(self, Self::Original)
@@ -729,6 +736,13 @@ impl_possibly_redacted_event!(
             }
         }
 
+        /// Returns this event's `state_key` field as a string, as it appears on the wire.
+        ///
+        /// This is useful for generic code that doesn't care about the type of the state key.
+        pub fn state_key_str(&self) -> &str {
+            self.state_key().as_ref()
+        }
+
         /// Get the inner `OriginalSyncStateEvent` if this is an unredacted event.
         pub fn as_original(&self) -> Option<&OriginalSyncStateEvent<C>> {
             as_variant!(self, Self::Original)
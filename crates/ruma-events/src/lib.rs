@@ -104,14 +104,23 @@
 
 #![warn(missing_docs)]
 
-use std::{collections::BTreeSet, fmt};
+use std::{collections::BTreeSet, fmt, io};
 
-use ruma_common::{EventEncryptionAlgorithm, OwnedUserId, RoomVersionId};
-use serde::{de::IgnoredAny, Deserialize, Serialize, Serializer};
+use ruma_common::{
+    redaction::{allowed_content_keys_for, AllowedKeys},
+    EventEncryptionAlgorithm, OwnedUserId, RoomVersionId,
+};
+use serde::{
+    de::{DeserializeOwned, IgnoredAny},
+    Deserialize, Serialize, Serializer,
+};
+use serde_json::Value as JsonValue;
 
 // Needs to be public for trybuild tests
 #[doc(hidden)]
 pub mod _custom;
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impls;
 mod content;
 mod enums;
 mod kinds;
@@ -177,6 +186,8 @@ pub mod secret_storage;
 pub mod space;
 pub mod sticker;
 pub mod tag;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod typing;
 #[cfg(feature = "unstable-msc3553")]
 pub mod video;
@@ -204,6 +215,68 @@ pub trait RedactContent {
     fn redact(self, version: &RoomVersionId) -> Self::Redacted;
 }
 
+/// Trait to query which keys of an event's content are preserved during redaction.
+///
+/// This allows to implement the [redaction algorithm] generically, without deserializing the
+/// content to its Rust type.
+///
+/// [redaction algorithm]: https://spec.matrix.org/latest/rooms/v11/#redactions
+pub trait RedactionAllowedKeys: StaticEventContent {
+    /// The keys of the content that are preserved when redacting an event with this content in a
+    /// room with the given version.
+    ///
+    /// The default implementation uses the rules of [`allowed_content_keys_for()`] for the event
+    /// type of this content.
+    fn redaction_allowed_keys(version: &RoomVersionId) -> &'static AllowedKeys {
+        allowed_content_keys_for(Self::TYPE, version)
+    }
+}
+
+/// Redacts the given possibly redacted state event content by only keeping the keys allowed by its
+/// [`RedactionAllowedKeys`] implementation.
+///
+/// # Errors
+///
+/// Returns an error if the content doesn't serialize to a JSON object, if a nested object with
+/// preserved keys is not a JSON object, or if the redacted JSON can't be deserialized back to the
+/// content type.
+pub fn redact_possibly<C>(content: C, version: &RoomVersionId) -> serde_json::Result<C>
+where
+    C: PossiblyRedactedStateEventContent + RedactionAllowedKeys + Serialize + DeserializeOwned,
+{
+    let JsonValue::Object(mut object) = serde_json::to_value(content)? else {
+        return Err(serde::ser::Error::custom("event content must be a JSON object"));
+    };
+
+    C::redaction_allowed_keys(version)
+        .retain_in_json(&mut object)
+        .map_err(serde::ser::Error::custom)?;
+
+    serde_json::from_value(JsonValue::Object(object))
+}
+
+/// The length in bytes of the JSON serialization of the given value.
+///
+/// The value is serialized into a writer that only counts the bytes, so no buffer is allocated.
+pub(crate) fn serialized_len<T: Serialize>(value: &T) -> serde_json::Result<usize> {
+    struct ByteCounter(usize);
+
+    impl io::Write for ByteCounter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0 += buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut counter = ByteCounter(0);
+    serde_json::to_writer(&mut counter, value)?;
+    Ok(counter.0)
+}
+
 /// Helper struct to determine the event kind from a `serde_json::value::RawValue`.
 #[doc(hidden)]
 #[derive(Deserialize)]
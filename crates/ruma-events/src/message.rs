@@ -210,18 +210,26 @@ impl TextContentBlock {
         self.0.is_empty()
     }
 
-    /// Get the plain text representation of this message.
-    pub fn find_plain(&self) -> Option<&str> {
+    /// Get the MIME types of the representations of this message, in order of preference.
+    pub fn mimetypes(&self) -> Vec<&str> {
+        self.iter().map(|content| content.mimetype.as_str()).collect()
+    }
+
+    /// Get the first representation of this message with the given MIME type.
+    pub fn representation(&self, mimetype: &str) -> Option<&str> {
         self.iter()
-            .find(|content| content.mimetype == "text/plain")
+            .find(|content| content.mimetype == mimetype)
             .map(|content| content.body.as_ref())
     }
 
+    /// Get the plain text representation of this message.
+    pub fn find_plain(&self) -> Option<&str> {
+        self.representation("text/plain")
+    }
+
     /// Get the HTML representation of this message.
     pub fn find_html(&self) -> Option<&str> {
-        self.iter()
-            .find(|content| content.mimetype == "text/html")
-            .map(|content| content.body.as_ref())
+        self.representation("text/html")
     }
 }
 
@@ -249,6 +249,33 @@ impl<E> BundledMessageLikeRelations<E> {
         self.replace.is_none() && self.thread.is_none() && self.reference.is_none()
     }
 
+    /// The bundled replacement, if any.
+    ///
+    /// See [`Self::has_replacement()`] for cases where this returns `None` even though the event
+    /// was replaced.
+    pub fn replace(&self) -> Option<&E> {
+        self.replace.as_deref()
+    }
+
+    /// The bundled thread, if this event is the root of a thread.
+    pub fn thread(&self) -> Option<&BundledThread> {
+        self.thread.as_deref()
+    }
+
+    /// The latest event in the thread, if this event is the root of a thread.
+    ///
+    /// This is useful to render a summary of the thread.
+    pub fn latest_thread_event(&self) -> Option<&Raw<AnyMessageLikeEvent>> {
+        self.thread().map(|thread| &thread.latest_event)
+    }
+
+    /// The bundled references to this event.
+    ///
+    /// Returns an empty slice if there are none.
+    pub fn references(&self) -> &[BundledReference] {
+        self.reference.as_deref().map(|r| r.chunk.as_slice()).unwrap_or_default()
+    }
+
     /// Transform `BundledMessageLikeRelations<E>` to `BundledMessageLikeRelations<T>` using the
     /// given closure to convert the `replace` field if it is `Some(_)`.
     pub(crate) fn map_replace<T>(self, f: impl FnOnce(E) -> T) -> BundledMessageLikeRelations<T> {
@@ -289,6 +316,18 @@ impl BundledStateRelations {
     pub fn is_empty(&self) -> bool {
         self.thread.is_none() && self.reference.is_none()
     }
+
+    /// The bundled thread, if this event is the root of a thread.
+    pub fn thread(&self) -> Option<&BundledThread> {
+        self.thread.as_deref()
+    }
+
+    /// The bundled references to this event.
+    ///
+    /// Returns an empty slice if there are none.
+    pub fn references(&self) -> &[BundledReference] {
+        self.reference.as_deref().map(|r| r.chunk.as_slice()).unwrap_or_default()
+    }
 }
 
 /// Relation types as defined in `rel_type` of an `m.relates_to` field.
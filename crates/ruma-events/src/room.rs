@@ -7,7 +7,7 @@ use std::collections::BTreeMap;
 use js_int::UInt;
 use ruma_common::{
     serde::{base64::UrlSafe, Base64},
-    OwnedMxcUri,
+    MxcUri, OwnedMxcUri,
 };
 use serde::{de, Deserialize, Serialize};
 
@@ -45,6 +45,19 @@ pub enum MediaSource {
     Encrypted(Box<EncryptedFile>),
 }
 
+impl MediaSource {
+    /// The MXC URI of the media file.
+    ///
+    /// If the media is encrypted, this is the URI of the encrypted file. Use [`MxcUri::parts()`] to
+    /// get the server name and media ID needed to download it.
+    pub fn mxc_uri(&self) -> &MxcUri {
+        match self {
+            Self::Plain(url) => url,
+            Self::Encrypted(file) => &file.url,
+        }
+    }
+}
+
 // Custom implementation of `Deserialize`, because serde doesn't guarantee what variant will be
 // deserialized for "externally tagged"¹ enums where multiple "tag" fields exist.
 //
@@ -111,6 +124,22 @@ impl ImageInfo {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// The MXC URI of the thumbnail of the image, if any.
+    ///
+    /// If the thumbnail is encrypted, this is the URI of the encrypted file. Use
+    /// [`ImageInfo::encrypted_thumbnail()`] to get the information needed to decrypt it.
+    pub fn thumbnail_url(&self) -> Option<&MxcUri> {
+        self.thumbnail_source.as_ref().map(MediaSource::mxc_uri)
+    }
+
+    /// The encryption info of the thumbnail of the image, if the thumbnail is encrypted.
+    pub fn encrypted_thumbnail(&self) -> Option<&EncryptedFile> {
+        match self.thumbnail_source.as_ref()? {
+            MediaSource::Plain(_) => None,
+            MediaSource::Encrypted(file) => Some(file),
+        }
+    }
 }
 
 /// Metadata about a thumbnail.
@@ -168,6 +197,60 @@ pub struct EncryptedFile {
     pub v: String,
 }
 
+impl EncryptedFile {
+    /// Creates an [`EncryptedFileBuilder`] to construct an `EncryptedFile`.
+    pub fn builder() -> EncryptedFileBuilder {
+        EncryptedFileBuilder::new()
+    }
+
+    /// Checks that the SHA-256 hash of the given ciphertext matches the `sha256` entry of
+    /// `hashes`.
+    ///
+    /// This should be called on the downloaded data before decrypting it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `hashes` doesn't contain a SHA-256 hash, or if it doesn't match the
+    /// hash of `data`.
+    #[cfg(feature = "media-integrity")]
+    pub fn verify_hash(&self, data: &[u8]) -> Result<(), IntegrityError> {
+        use sha2::{Digest, Sha256};
+
+        let expected = self.hashes.get("sha256").ok_or(IntegrityError::MissingSha256Hash)?;
+
+        if Sha256::digest(data).as_slice() == expected.as_bytes() {
+            Ok(())
+        } else {
+            Err(IntegrityError::HashMismatch)
+        }
+    }
+
+    /// The AES-256 key used to encrypt the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `k` field of the [`JsonWebKey`] is not 256 bits long.
+    pub fn jwk_key(&self) -> Result<[u8; 32], IntegrityError> {
+        let key = self.key.k.as_bytes();
+        key.try_into().map_err(|_| IntegrityError::InvalidKeyLength(key.len()))
+    }
+}
+
+/// An error encountered when checking the integrity of an [`EncryptedFile`].
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum IntegrityError {
+    /// The `hashes` don't contain a SHA-256 hash.
+    #[error("missing SHA-256 hash")]
+    MissingSha256Hash,
+    /// The SHA-256 hash of the data doesn't match the one in `hashes`.
+    #[error("SHA-256 hash mismatch")]
+    HashMismatch,
+    /// The key has the given length in bytes instead of 32.
+    #[error("invalid key length: expected 32 bytes, found {0}")]
+    InvalidKeyLength(usize),
+}
+
 /// Initial set of fields of `EncryptedFile`.
 ///
 /// This struct will not be updated even if additional fields are added to `EncryptedFile` in a new
@@ -202,6 +285,102 @@ impl From<EncryptedFileInit> for EncryptedFile {
     }
 }
 
+/// An [`EncryptedFile`] builder.
+///
+/// This type can be used to construct an `EncryptedFile` through a few method calls. Create it
+/// with [`EncryptedFile::builder()`].
+#[derive(Debug)]
+pub struct EncryptedFileBuilder {
+    url: Option<OwnedMxcUri>,
+    key: Option<JsonWebKey>,
+    iv: Option<Base64>,
+    hashes: BTreeMap<String, Base64>,
+}
+
+impl EncryptedFileBuilder {
+    fn new() -> Self {
+        Self { url: None, key: None, iv: None, hashes: BTreeMap::new() }
+    }
+
+    /// Set the URL to the file.
+    pub fn url(self, url: OwnedMxcUri) -> Self {
+        Self { url: Some(url), ..self }
+    }
+
+    /// Set the JSON Web Key.
+    pub fn key(self, key: JsonWebKey) -> Self {
+        Self { key: Some(key), ..self }
+    }
+
+    /// Set the JSON Web Key to an `A256CTR` key, with the given key data and the default values
+    /// of the other fields required by the spec.
+    pub fn aes_key(self, k: Base64<UrlSafe>) -> Self {
+        self.key(
+            JsonWebKeyInit {
+                kty: "oct".to_owned(),
+                key_ops: vec!["encrypt".to_owned(), "decrypt".to_owned()],
+                alg: "A256CTR".to_owned(),
+                k,
+                ext: true,
+            }
+            .into(),
+        )
+    }
+
+    /// Set the 128-bit unique counter block used by AES-CTR.
+    pub fn iv(self, iv: Base64) -> Self {
+        Self { iv: Some(iv), ..self }
+    }
+
+    /// Add a hash of the ciphertext with the given algorithm name.
+    pub fn hash(mut self, algorithm: String, hash: Base64) -> Self {
+        self.hashes.insert(algorithm, hash);
+        self
+    }
+
+    /// Add the SHA-256 hash of the ciphertext.
+    pub fn sha256(self, hash: Base64) -> Self {
+        self.hash("sha256".to_owned(), hash)
+    }
+
+    /// Finish building the [`EncryptedFile`].
+    ///
+    /// The version of the encrypted attachments protocol is set to `v2`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL, the key, the IV or all the hashes are missing.
+    pub fn build(self) -> Result<EncryptedFile, EncryptedFileBuilderError> {
+        let url = self.url.ok_or(EncryptedFileBuilderError::MissingUrl)?;
+        let key = self.key.ok_or(EncryptedFileBuilderError::MissingKey)?;
+        let iv = self.iv.ok_or(EncryptedFileBuilderError::MissingIv)?;
+
+        if self.hashes.is_empty() {
+            return Err(EncryptedFileBuilderError::MissingHashes);
+        }
+
+        Ok(EncryptedFile { url, key, iv, hashes: self.hashes, v: "v2".to_owned() })
+    }
+}
+
+/// An error encountered when building an [`EncryptedFile`] with an [`EncryptedFileBuilder`].
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum EncryptedFileBuilderError {
+    /// The URL is missing.
+    #[error("missing URL")]
+    MissingUrl,
+    /// The key is missing.
+    #[error("missing key")]
+    MissingKey,
+    /// The IV is missing.
+    #[error("missing IV")]
+    MissingIv,
+    /// There is no hash of the ciphertext.
+    #[error("missing hashes")]
+    MissingHashes,
+}
+
 /// A [JSON Web Key](https://tools.ietf.org/html/rfc7517#appendix-A.3) object.
 ///
 /// To create an instance of this type, first create a `JsonWebKeyInit` and convert it via
@@ -282,7 +461,10 @@ mod tests {
     use serde::Deserialize;
     use serde_json::{from_value as from_json_value, json};
 
-    use super::{EncryptedFile, JsonWebKey, MediaSource};
+    use super::{
+        EncryptedFile, EncryptedFileBuilderError, ImageInfo, IntegrityError, JsonWebKey,
+        MediaSource,
+    };
 
     #[derive(Deserialize)]
     struct MsgWithAttachment {
@@ -333,4 +515,85 @@ mod tests {
 
         assert_matches!(msg.source, MediaSource::Encrypted(_));
     }
+
+    #[test]
+    fn media_source_mxc_uri() {
+        let source = MediaSource::Plain(mxc_uri!("mxc://localhost/file").to_owned());
+        assert_eq!(source.mxc_uri().parts().unwrap(), ("localhost".try_into().unwrap(), "file"));
+
+        let source = MediaSource::Encrypted(Box::new(encrypted_file()));
+        assert_eq!(source.mxc_uri(), mxc_uri!("mxc://localhost/encryptedfile"));
+    }
+
+    #[test]
+    fn image_info_thumbnail() {
+        let mut info = ImageInfo::new();
+        assert_eq!(info.thumbnail_url(), None);
+        assert!(info.encrypted_thumbnail().is_none());
+
+        info.thumbnail_source =
+            Some(MediaSource::Plain(mxc_uri!("mxc://localhost/thumb").to_owned()));
+        assert_eq!(info.thumbnail_url(), Some(mxc_uri!("mxc://localhost/thumb")));
+        assert!(info.encrypted_thumbnail().is_none());
+
+        info.thumbnail_source = Some(MediaSource::Encrypted(Box::new(encrypted_file())));
+        assert_eq!(info.thumbnail_url(), Some(mxc_uri!("mxc://localhost/encryptedfile")));
+        assert_eq!(info.encrypted_thumbnail().unwrap().v, "v2");
+    }
+
+    #[test]
+    fn encrypted_file_jwk_key() {
+        let mut file = encrypted_file();
+        assert_eq!(file.jwk_key(), Err(IntegrityError::InvalidKeyLength(64)));
+
+        file.key.k = Base64::new(vec![1; 32]);
+        assert_eq!(file.jwk_key(), Ok([1; 32]));
+    }
+
+    #[test]
+    #[cfg(feature = "media-integrity")]
+    fn encrypted_file_verify_hash() {
+        let mut file = encrypted_file();
+        assert_eq!(file.verify_hash(b"hello"), Err(IntegrityError::MissingSha256Hash));
+
+        file.hashes.insert(
+            "sha256".to_owned(),
+            Base64::parse("LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ").unwrap(),
+        );
+        assert_eq!(file.verify_hash(b"hello"), Ok(()));
+        assert_eq!(file.verify_hash(b"hello!"), Err(IntegrityError::HashMismatch));
+    }
+
+    #[test]
+    fn encrypted_file_builder() {
+        let file = EncryptedFile::builder()
+            .url(mxc_uri!("mxc://localhost/encryptedfile").to_owned())
+            .aes_key(Base64::new(vec![1; 32]))
+            .iv(Base64::new(vec![2; 16]))
+            .sha256(Base64::new(vec![3; 32]))
+            .build()
+            .unwrap();
+
+        assert_eq!(file.url, "mxc://localhost/encryptedfile");
+        assert_eq!(file.key.kty, "oct");
+        assert_eq!(file.key.key_ops, ["encrypt", "decrypt"]);
+        assert_eq!(file.key.alg, "A256CTR");
+        assert!(file.key.ext);
+        assert_eq!(file.jwk_key(), Ok([1; 32]));
+        assert_eq!(file.hashes["sha256"].as_bytes(), [3; 32]);
+        assert_eq!(file.v, "v2");
+
+        assert_matches!(
+            EncryptedFile::builder().key(dummy_jwt()).build(),
+            Err(EncryptedFileBuilderError::MissingUrl)
+        );
+        assert_matches!(
+            EncryptedFile::builder()
+                .url(mxc_uri!("mxc://localhost/encryptedfile").to_owned())
+                .key(dummy_jwt())
+                .iv(Base64::new(vec![2; 16]))
+                .build(),
+            Err(EncryptedFileBuilderError::MissingHashes)
+        );
+    }
 }
@@ -4,7 +4,9 @@ use ruma_common::{OwnedRoomAliasId, OwnedServerName, RoomVersionId};
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
 
-use crate::{EventContent, RedactContent, RedactedStateEventContent, StateEventType};
+use crate::{
+    EventContent, RedactContent, RedactedStateEventContent, RedactionAllowedKeys, StateEventType,
+};
 
 /// The content of an `m.room.aliases` event.
 ///
@@ -43,6 +45,8 @@ impl RedactContent for RoomAliasesEventContent {
     }
 }
 
+impl RedactionAllowedKeys for RoomAliasesEventContent {}
+
 /// An aliases event that has been redacted.
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
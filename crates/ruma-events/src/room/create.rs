@@ -2,11 +2,20 @@
 //!
 //! [`m.room.create`]: https://spec.matrix.org/latest/client-server-api/#mroomcreate
 
-use ruma_common::{room::RoomType, OwnedEventId, OwnedRoomId, OwnedUserId, RoomVersionId};
+use std::{collections::BTreeSet, ops::Deref};
+
+use ruma_common::{
+    room::{JoinRuleKind, RoomType},
+    EventId, OwnedEventId, OwnedRoomId, OwnedUserId, RoomId, RoomVersionId, UserId,
+};
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
 
-use crate::{EmptyStateKey, RedactContent, RedactedStateEventContent};
+use crate::{
+    serialized_len, EmptyStateKey, EventContent, RedactContent, RedactedStateEventContent,
+    RedactionAllowedKeys, StateEventType, StaticEventContent,
+};
 
 /// The content of an `m.room.create` event.
 ///
@@ -28,17 +37,32 @@ pub struct RoomCreateEventContent {
     pub creator: Option<OwnedUserId>,
 
     /// Whether or not this room's data should be transferred to other homeservers.
+    ///
+    /// If you activate the `compat-federate-string` feature, this field can be decoded from the
+    /// strings `"true"` and `"false"`, instead of a boolean as it should be according to the
+    /// specification.
     #[serde(
         rename = "m.federate",
         default = "ruma_common::serde::default_true",
         skip_serializing_if = "ruma_common::serde::is_true"
     )]
+    #[cfg_attr(
+        feature = "compat-federate-string",
+        serde(deserialize_with = "ruma_common::serde::deserialize_as_bool_or_string")
+    )]
     pub federate: bool,
 
     /// The version of the room.
     ///
     /// Defaults to `RoomVersionId::V1`.
+    ///
+    /// If you activate the `compat-room-version-number` feature, this field can be decoded from a
+    /// number, instead of a string as it should be according to the specification.
     #[serde(default = "default_room_version_id")]
+    #[cfg_attr(
+        feature = "compat-room-version-number",
+        serde(deserialize_with = "deserialize_room_version_number_or_string")
+    )]
     pub room_version: RoomVersionId,
 
     /// A reference to the room this room replaces, if the previous room was upgraded.
@@ -50,6 +74,13 @@ pub struct RoomCreateEventContent {
     /// This is currently only used for spaces.
     #[serde(skip_serializing_if = "Option::is_none", rename = "type")]
     pub room_type: Option<RoomType>,
+
+    /// Additional creators of the room, who have the same privileges as the sender of this event.
+    ///
+    /// Added by [MSC4289](https://github.com/matrix-org/matrix-spec-proposals/pull/4289).
+    #[cfg(feature = "unstable-msc4289")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_creators: Vec<OwnedUserId>,
 }
 
 impl RoomCreateEventContent {
@@ -63,6 +94,8 @@ impl RoomCreateEventContent {
             room_version: default_room_version_id(),
             predecessor: None,
             room_type: None,
+            #[cfg(feature = "unstable-msc4289")]
+            additional_creators: Vec::new(),
         }
     }
 
@@ -78,7 +111,187 @@ impl RoomCreateEventContent {
             room_version: RoomVersionId::V11,
             predecessor: None,
             room_type: None,
+            #[cfg(feature = "unstable-msc4289")]
+            additional_creators: Vec::new(),
+        }
+    }
+
+    /// Creates a new `RoomCreateEventContent` with the default values for the given room version.
+    ///
+    /// The `creator` field is required by room versions 1 through 10 and was removed in room
+    /// version 11.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `creator` is missing for room versions 1 through 10, if it is set for
+    /// room version 11, or if the room version is not known.
+    pub fn for_version(
+        version: RoomVersionId,
+        creator: Option<OwnedUserId>,
+    ) -> Result<Self, VersionError> {
+        let mut content = match (version.capability_generation(), creator) {
+            (1..=10, Some(creator)) => Self::new_v1(creator),
+            (1..=10, None) => return Err(VersionError::MissingCreator),
+            (11, None) => Self::new_v11(),
+            (11, Some(_)) => return Err(VersionError::UnexpectedCreator),
+            _ => return Err(VersionError::UnsupportedVersion),
+        };
+
+        content.room_version = version;
+        Ok(content)
+    }
+
+    /// The creator of the room.
+    ///
+    /// Returns the `creator` field if it is set, as in room versions 1 through 10, and the given
+    /// `sender` of the event otherwise, as required since room version 11.
+    pub fn creator_or_sender<'a>(&'a self, sender: &'a UserId) -> &'a UserId {
+        #[allow(deprecated)]
+        self.creator.as_deref().unwrap_or(sender)
+    }
+
+    /// All the creators of the room: the creator returned by [`Self::creator_or_sender()`],
+    /// followed by the `additional_creators`.
+    ///
+    /// Duplicate user IDs are only returned once.
+    #[cfg(feature = "unstable-msc4289")]
+    pub fn all_creators<'a>(&'a self, sender: &'a UserId) -> Vec<&'a UserId> {
+        let mut creators = vec![self.creator_or_sender(sender)];
+
+        for creator in &self.additional_creators {
+            if !creators.contains(&&**creator) {
+                creators.push(creator);
+            }
+        }
+
+        creators
+    }
+
+    /// The keys of the JSON object this content serializes to, given the current values of its
+    /// fields.
+    ///
+    /// Keys of fields that are skipped during serialization, like `creator` when it is `None` or
+    /// `m.federate` when it is `true`, are not included.
+    pub fn content_keys(&self) -> Vec<&'static str> {
+        let mut keys = Vec::new();
+
+        #[allow(deprecated)]
+        if self.creator.is_some() {
+            keys.push("creator");
+        }
+        if !self.federate {
+            keys.push("m.federate");
+        }
+        keys.push("room_version");
+        if self.predecessor.is_some() {
+            keys.push("predecessor");
         }
+        if self.room_type.is_some() {
+            keys.push("type");
+        }
+        #[cfg(feature = "unstable-msc4289")]
+        if !self.additional_creators.is_empty() {
+            keys.push("additional_creators");
+        }
+
+        keys
+    }
+
+    /// The length in bytes of the JSON serialization of this content.
+    ///
+    /// The content is serialized into a writer that only counts the bytes, so no buffer is
+    /// allocated.
+    pub fn estimated_serialized_len(&self) -> usize {
+        serialized_len(self).expect("room create content should serialize")
+    }
+
+    /// The JSON of this content after it is redacted according to the rules of the given room
+    /// version.
+    ///
+    /// This applies [`RedactContent::redact()`] to a clone of this content, serializes it, and
+    /// only keeps the keys allowed by the redaction algorithm. This means that fields that are
+    /// reset to their default value by the redaction, like `room_version` before room version 11,
+    /// are removed rather than serialized with their default value.
+    pub fn redacted_json(&self, version: &RoomVersionId) -> JsonValue {
+        let redacted = self.clone().redact(version);
+        let mut json =
+            serde_json::to_value(redacted).expect("room create content should serialize");
+
+        if let JsonValue::Object(object) = &mut json {
+            Self::redaction_allowed_keys(version)
+                .retain_in_json(object)
+                .expect("room create content has no nested object with preserved keys");
+        }
+
+        json
+    }
+
+    /// Whether this room was created from scratch or as the upgrade of another room.
+    pub fn origin(&self) -> CreateEventOrigin {
+        match &self.predecessor {
+            None => CreateEventOrigin::Fresh,
+            Some(predecessor) => CreateEventOrigin::Upgraded(predecessor.clone()),
+        }
+    }
+
+    /// The kind of this room, according to its `room_type`.
+    pub fn room_kind(&self) -> RoomKind<'_> {
+        match &self.room_type {
+            None => RoomKind::Room,
+            Some(RoomType::Space) => RoomKind::Space,
+            Some(room_type) => RoomKind::Custom(room_type.as_str()),
+        }
+    }
+
+    /// Sets the reference to the room that was upgraded to this room.
+    pub fn with_predecessor(mut self, predecessor: PreviousRoom) -> Self {
+        self.predecessor = Some(predecessor);
+        self
+    }
+
+    /// Removes the reference to the room that was upgraded to this room.
+    pub fn clear_predecessor(mut self) -> Self {
+        self.predecessor = None;
+        self
+    }
+
+    /// Whether the room version of this room supports the `restricted` join rule.
+    ///
+    /// This is supported starting from room version 8. Room versions that are not known by Ruma
+    /// are assumed to not support it.
+    ///
+    /// This is a shortcut for [`RoomVersionId::supports_join_rule()`].
+    pub fn supports_restricted_join_rules(&self) -> bool {
+        self.room_version.supports_join_rule(&JoinRuleKind::Restricted)
+    }
+
+    /// Whether the room version of this room supports knocking.
+    ///
+    /// This is supported starting from room version 7. Room versions that are not known by Ruma
+    /// are assumed to not support it.
+    ///
+    /// This is a shortcut for [`RoomVersionId::supports_join_rule()`].
+    pub fn supports_knocking(&self) -> bool {
+        self.room_version.supports_join_rule(&JoinRuleKind::Knock)
+    }
+
+    /// Whether the given room ID is consistent with this content, on a best-effort basis.
+    ///
+    /// Room IDs are opaque, so this only checks the information that can be inferred from them:
+    ///
+    /// - The room IDs of all the room versions known by Ruma contain a server name.
+    /// - If `creator` is set, its server name must match the one of the room ID, since the room ID
+    ///   is generated by the server of the creator.
+    ///
+    /// This is conservative: it returns `true` when there is not enough information to tell.
+    pub fn consistent_with_room_id(&self, room_id: &RoomId) -> bool {
+        let Some(server_name) = room_id.server_name() else {
+            // Only custom room versions might use room IDs without a server name.
+            return self.room_version.capability_generation() == 0;
+        };
+
+        #[allow(deprecated)]
+        self.creator.as_ref().map_or(true, |creator| creator.server_name() == server_name)
     }
 }
 
@@ -87,26 +300,36 @@ impl RedactContent for RoomCreateEventContent {
 
     fn redact(self, version: &RoomVersionId) -> Self::Redacted {
         #[allow(deprecated)]
-        match version {
-            RoomVersionId::V1
-            | RoomVersionId::V2
-            | RoomVersionId::V3
-            | RoomVersionId::V4
-            | RoomVersionId::V5
-            | RoomVersionId::V6
-            | RoomVersionId::V7
-            | RoomVersionId::V8
-            | RoomVersionId::V9
-            | RoomVersionId::V10 => Self {
-                room_version: default_room_version_id(),
+        let content = match version.capability_generation() {
+            1..=10 => Self {
                 creator: self.creator,
+                room_version: default_room_version_id(),
                 ..Self::new_v11()
             },
             _ => self,
-        }
+        };
+
+        RedactedRoomCreateEventContent(content)
     }
 }
 
+impl RedactionAllowedKeys for RoomCreateEventContent {}
+
+/// An error encountered when creating a [`RoomCreateEventContent`] for a given room version.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum VersionError {
+    /// The `creator` is missing, but it is required by the room version.
+    #[error("the room version requires a creator")]
+    MissingCreator,
+    /// The `creator` is set, but it was removed in the room version.
+    #[error("the room version doesn't allow a creator")]
+    UnexpectedCreator,
+    /// The room version is not known.
+    #[error("unsupported room version")]
+    UnsupportedVersion,
+}
+
 /// A reference to an old room replaced during a room version upgrade.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
@@ -115,14 +338,115 @@ pub struct PreviousRoom {
     pub room_id: OwnedRoomId,
 
     /// The event ID of the last known event in the old room.
+    #[cfg(not(feature = "compat-optional"))]
     pub event_id: OwnedEventId,
+
+    /// The event ID of the last known event in the old room.
+    ///
+    /// This field is optional because the `compat-optional` feature is enabled, since some early
+    /// rooms omit it.
+    #[cfg(feature = "compat-optional")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<OwnedEventId>,
 }
 
 impl PreviousRoom {
     /// Creates a new `PreviousRoom` from the given room and event IDs.
     pub fn new(room_id: OwnedRoomId, event_id: OwnedEventId) -> Self {
+        #[cfg(feature = "compat-optional")]
+        let event_id = Some(event_id);
+
         Self { room_id, event_id }
     }
+
+    /// The event ID of the last known event in the old room.
+    ///
+    /// This is always `Some(_)`, unless the `compat-optional` feature is enabled and the
+    /// `event_id` is missing.
+    pub fn last_event(&self) -> Option<&EventId> {
+        #[cfg(not(feature = "compat-optional"))]
+        let event_id = Some(&*self.event_id);
+        #[cfg(feature = "compat-optional")]
+        let event_id = self.event_id.as_deref();
+
+        event_id
+    }
+}
+
+/// The origin of a room, as returned by [`RoomCreateEventContent::origin()`].
+#[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum CreateEventOrigin {
+    /// The room was created from scratch.
+    Fresh,
+
+    /// The room replaces the given room, that was upgraded.
+    Upgraded(PreviousRoom),
+}
+
+/// The kind of a room, as returned by [`RoomCreateEventContent::room_kind()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum RoomKind<'a> {
+    /// A regular room, without a `room_type`.
+    Room,
+
+    /// A space.
+    Space,
+
+    /// A room with a custom `room_type`.
+    Custom(&'a str),
+}
+
+/// An iterator over the predecessors of a room, from the most recent one to the original room.
+///
+/// The `m.room.create` event of each room is fetched lazily with the closure given to
+/// [`PredecessorChain::new()`]. The iteration stops when a room has no predecessor, when the
+/// create event of a room could not be fetched, or when a room appears twice in the chain.
+pub struct PredecessorChain<F> {
+    /// The room whose predecessor should be returned next.
+    room_id: Option<OwnedRoomId>,
+
+    /// The rooms that were already visited, to avoid infinite loops.
+    visited: BTreeSet<OwnedRoomId>,
+
+    /// The closure to fetch the content of the `m.room.create` event of a room.
+    fetch: F,
+}
+
+impl<F> PredecessorChain<F>
+where
+    F: FnMut(&RoomId) -> Option<RoomCreateEventContent>,
+{
+    /// Creates a new `PredecessorChain` starting at the room with the given ID.
+    ///
+    /// `fetch` is called with the ID of a room to get the content of its `m.room.create` event.
+    pub fn new(room_id: &RoomId, fetch: F) -> Self {
+        Self {
+            room_id: Some(room_id.to_owned()),
+            visited: BTreeSet::from([room_id.to_owned()]),
+            fetch,
+        }
+    }
+}
+
+impl<F> Iterator for PredecessorChain<F>
+where
+    F: FnMut(&RoomId) -> Option<RoomCreateEventContent>,
+{
+    type Item = PreviousRoom;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let room_id = self.room_id.take()?;
+        let predecessor = (self.fetch)(&room_id)?.predecessor?;
+
+        if !self.visited.insert(predecessor.room_id.clone()) {
+            return None;
+        }
+
+        self.room_id = Some(predecessor.room_id.clone());
+        Some(predecessor)
+    }
 }
 
 /// Used to default the `room_version` field to room version 1.
@@ -130,6 +454,37 @@ fn default_room_version_id() -> RoomVersionId {
     RoomVersionId::V1
 }
 
+/// Take either a string or an unsigned integer and deserialize to a `RoomVersionId`.
+#[cfg(feature = "compat-room-version-number")]
+fn deserialize_room_version_number_or_string<'de, D>(de: D) -> Result<RoomVersionId, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use std::fmt;
+
+    use serde::de::{self, Visitor};
+
+    struct RoomVersionVisitor;
+
+    impl<'de> Visitor<'de> for RoomVersionVisitor {
+        type Value = RoomVersionId;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a room version as a string or an unsigned integer")
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            RoomVersionId::try_from(v.to_string()).map_err(E::custom)
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            RoomVersionId::try_from(v).map_err(E::custom)
+        }
+    }
+
+    de.deserialize_any(RoomVersionVisitor)
+}
+
 /// Redacted form of [`RoomCreateEventContent`].
 ///
 /// The redaction rules of this event changed with room version 11:
@@ -138,19 +493,65 @@ fn default_room_version_id() -> RoomVersionId {
 ///   from room version 11 the field is removed.
 /// - In room versions 1 through 10, all the other fields were redacted, starting from room version
 ///   11 all the fields are preserved.
-pub type RedactedRoomCreateEventContent = RoomCreateEventContent;
+///
+/// Since the redacted fields all have a default value, this is a wrapper around a
+/// [`RoomCreateEventContent`] that dereferences to it, and that can be converted from and into it
+/// with its `From` implementations.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct RedactedRoomCreateEventContent(RoomCreateEventContent);
+
+impl EventContent for RedactedRoomCreateEventContent {
+    type EventType = StateEventType;
+
+    fn event_type(&self) -> Self::EventType {
+        StateEventType::RoomCreate
+    }
+}
+
+impl StaticEventContent for RedactedRoomCreateEventContent {
+    const TYPE: &'static str = "m.room.create";
+}
 
 impl RedactedStateEventContent for RedactedRoomCreateEventContent {
     type StateKey = EmptyStateKey;
 }
 
+impl Deref for RedactedRoomCreateEventContent {
+    type Target = RoomCreateEventContent;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<RoomCreateEventContent> for RedactedRoomCreateEventContent {
+    fn from(c: RoomCreateEventContent) -> Self {
+        Self(c)
+    }
+}
+
+impl From<RedactedRoomCreateEventContent> for RoomCreateEventContent {
+    fn from(c: RedactedRoomCreateEventContent) -> Self {
+        c.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use assert_matches2::assert_matches;
-    use ruma_common::{owned_user_id, RoomVersionId};
-    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+    use ruma_common::{
+        owned_event_id, owned_room_id, owned_user_id, room_id, RoomId, RoomVersionId,
+    };
+    use serde_json::{
+        from_value as from_json_value, json, to_value as to_json_value, Value as JsonValue,
+    };
 
-    use super::{RoomCreateEventContent, RoomType};
+    use super::{
+        CreateEventOrigin, PredecessorChain, PreviousRoom, RoomCreateEventContent, RoomKind,
+        RoomType, VersionError,
+    };
+    use crate::{RedactContent, RedactionAllowedKeys};
 
     #[test]
     fn serialization() {
@@ -161,6 +562,8 @@ mod tests {
             room_version: RoomVersionId::V4,
             predecessor: None,
             room_type: None,
+            #[cfg(feature = "unstable-msc4289")]
+            additional_creators: Vec::new(),
         };
 
         let json = json!({
@@ -181,6 +584,8 @@ mod tests {
             room_version: RoomVersionId::V4,
             predecessor: None,
             room_type: Some(RoomType::Space),
+            #[cfg(feature = "unstable-msc4289")]
+            additional_creators: Vec::new(),
         };
 
         let json = json!({
@@ -227,4 +632,378 @@ mod tests {
         assert_matches!(content.predecessor, None);
         assert_eq!(content.room_type, Some(RoomType::Space));
     }
+
+    #[test]
+    #[allow(deprecated)]
+    fn redaction_keeps_creator_before_v11() {
+        let mut content = RoomCreateEventContent::new_v1(owned_user_id!("@carl:example.com"));
+        content.federate = false;
+        content.room_version = RoomVersionId::V10;
+        content.predecessor = Some(PreviousRoom::new(
+            owned_room_id!("!old:example.com"),
+            owned_event_id!("$last:example.com"),
+        ));
+        content.room_type = Some(RoomType::Space);
+
+        for version in [RoomVersionId::V1, RoomVersionId::V6, RoomVersionId::V10] {
+            let redacted = content.clone().redact(&version);
+
+            assert_eq!(redacted.creator.as_deref().unwrap(), "@carl:example.com");
+            assert!(redacted.federate);
+            assert_eq!(redacted.room_version, RoomVersionId::V1);
+            assert_matches!(redacted.predecessor, None);
+            assert_eq!(redacted.room_type, None);
+        }
+    }
+
+    #[test]
+    fn redaction_keeps_everything_since_v11() {
+        let mut content = RoomCreateEventContent::new_v11();
+        content.federate = false;
+        content.predecessor = Some(PreviousRoom::new(
+            owned_room_id!("!old:example.com"),
+            owned_event_id!("$last:example.com"),
+        ));
+        content.room_type = Some(RoomType::Space);
+
+        let redacted = content.redact(&RoomVersionId::V11);
+        let json = json!({
+            "m.federate": false,
+            "room_version": "11",
+            "predecessor": {
+                "room_id": "!old:example.com",
+                "event_id": "$last:example.com",
+            },
+            "type": "m.space",
+        });
+        assert_eq!(to_json_value(&redacted).unwrap(), json);
+
+        let content = RoomCreateEventContent::from(redacted);
+        assert_eq!(to_json_value(&content).unwrap(), json);
+    }
+
+    #[test]
+    fn room_version_capabilities() {
+        let mut content = RoomCreateEventContent::new_v1(owned_user_id!("@carl:example.com"));
+        assert!(!content.supports_knocking());
+        assert!(!content.supports_restricted_join_rules());
+
+        content.room_version = RoomVersionId::V7;
+        assert!(content.supports_knocking());
+        assert!(!content.supports_restricted_join_rules());
+
+        content.room_version = RoomVersionId::V8;
+        assert!(content.supports_knocking());
+        assert!(content.supports_restricted_join_rules());
+
+        let mut content = RoomCreateEventContent::new_v11();
+        assert!(content.supports_knocking());
+        assert!(content.supports_restricted_join_rules());
+
+        content.room_version = "org.example.custom".try_into().unwrap();
+        assert!(!content.supports_knocking());
+        assert!(!content.supports_restricted_join_rules());
+    }
+
+    #[test]
+    fn redaction_allowed_keys() {
+        let allowed_keys = RoomCreateEventContent::redaction_allowed_keys(&RoomVersionId::V10);
+        assert!(!allowed_keys.is_all());
+        assert_eq!(allowed_keys.keys(), ["creator"]);
+        assert!(allowed_keys.nested().is_empty());
+
+        assert!(RoomCreateEventContent::redaction_allowed_keys(&RoomVersionId::V11).is_all());
+    }
+
+    #[test]
+    fn with_and_clear_predecessor() {
+        let content = RoomCreateEventContent::new_v11().with_predecessor(PreviousRoom::new(
+            owned_room_id!("!old:example.com"),
+            owned_event_id!("$last:example.com"),
+        ));
+        assert_eq!(
+            to_json_value(&content).unwrap(),
+            json!({
+                "room_version": "11",
+                "predecessor": {
+                    "room_id": "!old:example.com",
+                    "event_id": "$last:example.com",
+                },
+            })
+        );
+
+        let content = content.clear_predecessor();
+        assert!(content.predecessor.is_none());
+        assert_eq!(to_json_value(&content).unwrap(), json!({ "room_version": "11" }));
+    }
+
+    #[test]
+    fn deserialize_federate() {
+        let json = json!({ "m.federate": false, "room_version": "11" });
+        let content = from_json_value::<RoomCreateEventContent>(json).unwrap();
+        assert!(!content.federate);
+
+        let json = json!({ "m.federate": "false", "room_version": "11" });
+        #[cfg(not(feature = "compat-federate-string"))]
+        from_json_value::<RoomCreateEventContent>(json).unwrap_err();
+        #[cfg(feature = "compat-federate-string")]
+        {
+            let content = from_json_value::<RoomCreateEventContent>(json).unwrap();
+            assert!(!content.federate);
+        }
+    }
+
+    #[test]
+    fn creator_or_sender() {
+        let sender = owned_user_id!("@sender:example.com");
+
+        let content = RoomCreateEventContent::new_v1(owned_user_id!("@carl:example.com"));
+        assert_eq!(content.creator_or_sender(&sender), "@carl:example.com");
+
+        let content = RoomCreateEventContent::new_v11();
+        assert_eq!(content.creator_or_sender(&sender), "@sender:example.com");
+    }
+
+    #[test]
+    fn predecessor_chain() {
+        let fetch = |room_id: &RoomId| {
+            let predecessor = match room_id.as_str() {
+                "!c:localhost" => Some(("!b:localhost", "$last_b")),
+                "!b:localhost" => Some(("!a:localhost", "$last_a")),
+                "!a:localhost" => None,
+                // Creates a loop.
+                "!y:localhost" => Some(("!x:localhost", "$last_x")),
+                "!x:localhost" => Some(("!y:localhost", "$last_y")),
+                _ => return None,
+            };
+
+            let content = RoomCreateEventContent::new_v11();
+            Some(match predecessor {
+                Some((room_id, event_id)) => content.with_predecessor(PreviousRoom::new(
+                    room_id.try_into().unwrap(),
+                    event_id.try_into().unwrap(),
+                )),
+                None => content,
+            })
+        };
+
+        let chain: Vec<_> = PredecessorChain::new(room_id!("!c:localhost"), fetch)
+            .map(|previous| (previous.room_id.clone(), previous.last_event().unwrap().to_owned()))
+            .collect();
+        assert_eq!(
+            chain,
+            [
+                (owned_room_id!("!b:localhost"), owned_event_id!("$last_b")),
+                (owned_room_id!("!a:localhost"), owned_event_id!("$last_a")),
+            ]
+        );
+
+        assert_eq!(PredecessorChain::new(room_id!("!unknown:localhost"), fetch).count(), 0);
+        assert_eq!(PredecessorChain::new(room_id!("!x:localhost"), fetch).count(), 1);
+    }
+
+    #[test]
+    fn deserialize_room_version_number() {
+        let json = json!({ "room_version": 11 });
+
+        #[cfg(feature = "compat-room-version-number")]
+        assert_eq!(
+            from_json_value::<RoomCreateEventContent>(json).unwrap().room_version,
+            RoomVersionId::V11
+        );
+        #[cfg(not(feature = "compat-room-version-number"))]
+        from_json_value::<RoomCreateEventContent>(json).unwrap_err();
+    }
+
+    #[test]
+    fn origin() {
+        let content = RoomCreateEventContent::new_v11();
+        assert_matches!(content.origin(), CreateEventOrigin::Fresh);
+
+        let content = content.with_predecessor(PreviousRoom::new(
+            owned_room_id!("!old:example.com"),
+            owned_event_id!("$last:example.com"),
+        ));
+        assert_matches!(content.origin(), CreateEventOrigin::Upgraded(predecessor));
+        assert_eq!(predecessor.room_id, "!old:example.com");
+        assert_eq!(predecessor.last_event().unwrap(), "$last:example.com");
+    }
+
+    #[test]
+    fn predecessor_without_event_id() {
+        let json = json!({
+            "room_version": "1",
+            "predecessor": {
+                "room_id": "!old:example.com",
+            },
+        });
+
+        #[cfg(feature = "compat-optional")]
+        {
+            let content = from_json_value::<RoomCreateEventContent>(json.clone()).unwrap();
+            let predecessor = content.predecessor.as_ref().unwrap();
+            assert_eq!(predecessor.room_id, "!old:example.com");
+            assert_eq!(predecessor.last_event(), None);
+            assert_eq!(to_json_value(&content).unwrap(), json);
+        }
+        #[cfg(not(feature = "compat-optional"))]
+        from_json_value::<RoomCreateEventContent>(json).unwrap_err();
+    }
+
+    #[test]
+    fn room_kind() {
+        let mut content = RoomCreateEventContent::new_v11();
+        assert_eq!(content.room_kind(), RoomKind::Room);
+
+        content.room_type = Some(RoomType::Space);
+        assert_eq!(content.room_kind(), RoomKind::Space);
+
+        content.room_type = Some("org.example.custom".into());
+        assert_eq!(content.room_kind(), RoomKind::Custom("org.example.custom"));
+    }
+
+    #[test]
+    #[cfg(feature = "unstable-msc4289")]
+    fn additional_creators() {
+        use ruma_common::user_id;
+
+        let json = json!({
+            "room_version": "11",
+            "additional_creators": ["@bob:example.org", "@alice:example.org"],
+        });
+        let content = from_json_value::<RoomCreateEventContent>(json.clone()).unwrap();
+        assert_eq!(
+            content.additional_creators,
+            [owned_user_id!("@bob:example.org"), owned_user_id!("@alice:example.org")]
+        );
+        assert_eq!(to_json_value(&content).unwrap(), json);
+
+        assert_eq!(
+            content.all_creators(user_id!("@alice:example.org")),
+            [user_id!("@alice:example.org"), user_id!("@bob:example.org")]
+        );
+
+        let redacted = content.clone().redact(&RoomVersionId::V11);
+        assert_eq!(redacted.additional_creators.len(), 2);
+        let redacted = content.redact(&RoomVersionId::V10);
+        assert!(redacted.additional_creators.is_empty());
+    }
+
+    #[test]
+    fn for_version() {
+        let creator = owned_user_id!("@carl:example.com");
+
+        let content =
+            RoomCreateEventContent::for_version(RoomVersionId::V1, Some(creator.clone())).unwrap();
+        assert_eq!(content.room_version, RoomVersionId::V1);
+        assert_eq!(content.creator.as_ref(), Some(&creator));
+
+        let content =
+            RoomCreateEventContent::for_version(RoomVersionId::V10, Some(creator.clone())).unwrap();
+        assert_eq!(content.room_version, RoomVersionId::V10);
+        assert_eq!(content.creator.as_ref(), Some(&creator));
+        assert_matches!(
+            RoomCreateEventContent::for_version(RoomVersionId::V10, None),
+            Err(VersionError::MissingCreator)
+        );
+
+        let content = RoomCreateEventContent::for_version(RoomVersionId::V11, None).unwrap();
+        assert_eq!(content.room_version, RoomVersionId::V11);
+        assert_eq!(content.creator, None);
+        assert_matches!(
+            RoomCreateEventContent::for_version(RoomVersionId::V11, Some(creator.clone())),
+            Err(VersionError::UnexpectedCreator)
+        );
+
+        let custom = RoomVersionId::try_from("org.example.custom").unwrap();
+        assert_matches!(
+            RoomCreateEventContent::for_version(custom, Some(creator)),
+            Err(VersionError::UnsupportedVersion)
+        );
+    }
+
+    #[test]
+    fn content_keys() {
+        fn serialized_keys(content: &RoomCreateEventContent) -> Vec<String> {
+            let JsonValue::Object(object) = to_json_value(content).unwrap() else {
+                panic!("content should serialize to an object");
+            };
+            object.keys().cloned().collect()
+        }
+
+        let content = RoomCreateEventContent::new_v11();
+        assert_eq!(content.content_keys(), ["room_version"]);
+        assert_eq!(content.content_keys(), serialized_keys(&content));
+
+        let mut content = RoomCreateEventContent::new_v1(owned_user_id!("@carl:example.com"))
+            .with_predecessor(PreviousRoom::new(
+                owned_room_id!("!old:example.com"),
+                owned_event_id!("$last:example.com"),
+            ));
+        content.federate = false;
+        content.room_type = Some(RoomType::Space);
+
+        let mut keys = content.content_keys();
+        assert_eq!(keys, ["creator", "m.federate", "room_version", "predecessor", "type"]);
+        keys.sort_unstable();
+        assert_eq!(keys, serialized_keys(&content));
+    }
+
+    #[test]
+    fn redacted_json() {
+        let mut content = RoomCreateEventContent::new_v1(owned_user_id!("@carl:example.com"))
+            .with_predecessor(PreviousRoom::new(
+                owned_room_id!("!old:example.com"),
+                owned_event_id!("$last:example.com"),
+            ));
+        content.federate = false;
+        content.room_version = RoomVersionId::V9;
+
+        assert_eq!(
+            content.redacted_json(&RoomVersionId::V9),
+            json!({ "creator": "@carl:example.com" })
+        );
+        assert_eq!(
+            content.redacted_json(&RoomVersionId::V11),
+            json!({
+                "creator": "@carl:example.com",
+                "m.federate": false,
+                "room_version": "9",
+                "predecessor": {
+                    "room_id": "!old:example.com",
+                    "event_id": "$last:example.com",
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn estimated_serialized_len() {
+        let content = RoomCreateEventContent::new_v1(owned_user_id!("@carl:example.com"))
+            .with_predecessor(PreviousRoom::new(
+                owned_room_id!("!old:example.com"),
+                owned_event_id!("$last:example.com"),
+            ));
+
+        assert_eq!(
+            content.estimated_serialized_len(),
+            serde_json::to_string(&content).unwrap().len()
+        );
+    }
+
+    #[test]
+    fn consistent_with_room_id() {
+        let content = RoomCreateEventContent::new_v1(owned_user_id!("@carl:example.com"));
+        assert!(content.consistent_with_room_id(room_id!("!abc:example.com")));
+        assert!(!content.consistent_with_room_id(room_id!("!abc:other.example.com")));
+        assert!(!content.consistent_with_room_id(room_id!("!abc")));
+
+        let content = RoomCreateEventContent::new_v11();
+        assert!(content.consistent_with_room_id(room_id!("!abc:other.example.com")));
+        assert!(!content.consistent_with_room_id(room_id!("!abc")));
+
+        let mut content = RoomCreateEventContent::new_v11();
+        content.room_version = "org.example.custom".try_into().unwrap();
+        assert!(content.consistent_with_room_id(room_id!("!abc")));
+    }
 }
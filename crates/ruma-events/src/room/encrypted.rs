@@ -108,6 +108,20 @@ pub enum Relation {
 }
 
 impl Relation {
+    /// Creates a new `Relation::Annotation` to the given event with the given key, as used by
+    /// reactions.
+    pub fn annotation(event_id: OwnedEventId, key: String) -> Self {
+        Self::Annotation(Annotation::new(event_id, key))
+    }
+
+    /// The ID of the annotated event and the key of the annotation, if this is an annotation.
+    pub fn as_annotation(&self) -> Option<(&EventId, &str)> {
+        match self {
+            Relation::Annotation(a) => Some((&a.event_id, &a.key)),
+            _ => None,
+        }
+    }
+
     /// The type of this `Relation`.
     ///
     /// Returns an `Option` because the `Reply` relation does not have a`rel_type` field.
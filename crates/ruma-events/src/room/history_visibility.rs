@@ -6,7 +6,7 @@ use ruma_common::serde::StringEnum;
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
 
-use crate::{EmptyStateKey, PrivOwnedStr};
+use crate::{EmptyStateKey, PrivOwnedStr, RedactionAllowedKeys};
 
 /// The content of an `m.room.history_visibility` event.
 ///
@@ -48,6 +48,8 @@ impl SyncRoomHistoryVisibilityEvent {
     }
 }
 
+impl RedactionAllowedKeys for RoomHistoryVisibilityEventContent {}
+
 /// Who can see a room's history.
 #[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/doc/string_enum.md"))]
 #[derive(Clone, PartialEq, Eq, StringEnum)]
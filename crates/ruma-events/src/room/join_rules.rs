@@ -4,7 +4,7 @@
 
 use std::{borrow::Cow, collections::BTreeMap};
 
-use ruma_common::{serde::from_raw_json_value, OwnedRoomId};
+use ruma_common::{room::JoinRuleKind, serde::from_raw_json_value, OwnedRoomId};
 use ruma_macros::EventContent;
 use serde::{
     de::{Deserializer, Error},
@@ -12,7 +12,7 @@ use serde::{
 };
 use serde_json::{value::RawValue as RawJsonValue, Value as JsonValue};
 
-use crate::{EmptyStateKey, PrivOwnedStr};
+use crate::{EmptyStateKey, PrivOwnedStr, RedactionAllowedKeys};
 
 /// The content of an `m.room.join_rules` event.
 ///
@@ -27,6 +27,8 @@ pub struct RoomJoinRulesEventContent {
     pub join_rule: JoinRule,
 }
 
+impl RedactionAllowedKeys for RoomJoinRulesEventContent {}
+
 impl RoomJoinRulesEventContent {
     /// Creates a new `RoomJoinRulesEventContent` with the given rule.
     pub fn new(join_rule: JoinRule) -> Self {
@@ -125,6 +127,19 @@ impl JoinRule {
             JoinRule::_Custom(rule) => &rule.0,
         }
     }
+
+    /// The kind of this `JoinRule`, without its configuration.
+    pub fn kind(&self) -> JoinRuleKind {
+        match self {
+            JoinRule::Invite => JoinRuleKind::Invite,
+            JoinRule::Knock => JoinRuleKind::Knock,
+            JoinRule::Private => JoinRuleKind::Private,
+            JoinRule::Restricted(_) => JoinRuleKind::Restricted,
+            JoinRule::KnockRestricted(_) => JoinRuleKind::KnockRestricted,
+            JoinRule::Public => JoinRuleKind::Public,
+            JoinRule::_Custom(rule) => rule.0.as_ref().into(),
+        }
+    }
 }
 
 impl<'de> Deserialize<'de> for JoinRule {
@@ -247,9 +262,11 @@ impl<'de> Deserialize<'de> for AllowRule {
 #[cfg(test)]
 mod tests {
     use assert_matches2::assert_matches;
-    use ruma_common::owned_room_id;
+    use ruma_common::{owned_room_id, room::JoinRuleKind};
 
-    use super::{AllowRule, JoinRule, OriginalSyncRoomJoinRulesEvent, RoomJoinRulesEventContent};
+    use super::{
+        AllowRule, JoinRule, OriginalSyncRoomJoinRulesEvent, Restricted, RoomJoinRulesEventContent,
+    };
 
     #[test]
     fn deserialize() {
@@ -315,4 +332,16 @@ mod tests {
         assert_matches!(&allow_rule, AllowRule::_Custom(_));
         assert_eq!(serde_json::to_string(&allow_rule).unwrap(), json);
     }
+
+    #[test]
+    fn join_rule_kind() {
+        assert_eq!(JoinRule::Public.kind(), JoinRuleKind::Public);
+        assert_eq!(
+            JoinRule::KnockRestricted(Restricted::new(vec![])).kind(),
+            JoinRuleKind::KnockRestricted
+        );
+
+        let rule: JoinRule = serde_json::from_str(r#"{"join_rule": "org.example.rule"}"#).unwrap();
+        assert_eq!(rule.kind().as_str(), "org.example.rule");
+    }
 }
@@ -15,7 +15,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     AnyStrippedStateEvent, BundledStateRelations, EventContent, PossiblyRedactedStateEventContent,
-    PrivOwnedStr, RedactContent, RedactedStateEventContent, StateEventType,
+    PrivOwnedStr, RedactContent, RedactedStateEventContent, RedactionAllowedKeys, StateEventType,
 };
 
 mod change;
@@ -182,6 +182,8 @@ impl RedactContent for RoomMemberEventContent {
     }
 }
 
+impl RedactionAllowedKeys for RoomMemberEventContent {}
+
 /// The possibly redacted form of [`RoomMemberEventContent`].
 ///
 /// This type is used when it's not obvious whether the content is redacted or not.
@@ -582,12 +584,12 @@ mod tests {
     use maplit::btreemap;
     use ruma_common::{
         mxc_uri, owned_server_signing_key_id, serde::CanBeEmpty, server_name, user_id,
-        MilliSecondsSinceUnixEpoch,
+        MilliSecondsSinceUnixEpoch, RoomVersionId,
     };
-    use serde_json::{from_value as from_json_value, json};
+    use serde_json::{from_value as from_json_value, json, Value as JsonValue};
 
     use super::{MembershipState, RoomMemberEventContent};
-    use crate::OriginalStateEvent;
+    use crate::{redact_possibly, OriginalStateEvent, RedactionAllowedKeys};
 
     #[test]
     fn serde_with_no_prev_content() {
@@ -820,4 +822,46 @@ mod tests {
             Some(user_id!("@notcarl:example.com"))
         );
     }
+
+    #[test]
+    fn redaction_allowed_keys() {
+        let content = json!({
+            "displayname": "alice",
+            "membership": "invite",
+            "third_party_invite": {
+                "display_name": "alice",
+                "signed": {
+                    "mxid": "@alice:example.org",
+                    "signatures": {},
+                    "token": "abc123",
+                },
+            },
+        });
+        let JsonValue::Object(mut object) = content else { unreachable!() };
+
+        RoomMemberEventContent::redaction_allowed_keys(&RoomVersionId::V11)
+            .retain_in_json(&mut object)
+            .unwrap();
+        assert_eq!(
+            JsonValue::Object(object),
+            json!({
+                "membership": "invite",
+                "third_party_invite": {
+                    "signed": {
+                        "mxid": "@alice:example.org",
+                        "signatures": {},
+                        "token": "abc123",
+                    },
+                },
+            })
+        );
+
+        let content = RoomMemberEventContent {
+            displayname: Some("alice".to_owned()),
+            ..RoomMemberEventContent::new(MembershipState::Join)
+        };
+        let redacted = redact_possibly(content, &RoomVersionId::V10).unwrap();
+        assert_eq!(redacted.displayname, None);
+        assert_eq!(redacted.membership, MembershipState::Join);
+    }
 }
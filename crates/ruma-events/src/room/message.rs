@@ -6,7 +6,7 @@ use std::borrow::Cow;
 
 use ruma_common::{
     serde::{JsonObject, Raw, StringEnum},
-    OwnedEventId, RoomId,
+    EventId, OwnedEventId, RoomId,
 };
 #[cfg(feature = "html")]
 use ruma_html::{sanitize_html, HtmlSanitizerMode, RemoveReplyFallback};
@@ -15,12 +15,10 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use tracing::warn;
 
-use self::reply::OriginalEventData;
-#[cfg(feature = "html")]
-use self::sanitize::remove_plain_reply_fallback;
+use self::{reply::OriginalEventData, sanitize::remove_plain_reply_fallback};
 use crate::{
     relation::{InReplyTo, Replacement, Thread},
-    AnySyncTimelineEvent, Mentions, PrivOwnedStr,
+    serialized_len, AnySyncTimelineEvent, Mentions, PrivOwnedStr,
 };
 
 mod audio;
@@ -58,6 +56,21 @@ pub use self::{
     without_relation::RoomMessageEventContentWithoutRelation,
 };
 
+/// The maximum length in bytes of the `body` of a [`MessageType`] accepted during
+/// deserialization.
+///
+/// This is the maximum size of an event according to the spec, so any longer `body` can't be
+/// valid.
+///
+/// The length is checked on the `body` string borrowed from the JSON, before the content of the
+/// message type is built. It doesn't limit the size of the JSON that is buffered to deserialize
+/// the content, so the size of incoming data should still be limited at the transport level.
+///
+/// Cargo features are additive, so enabling the `limit-message-body-len` feature in any crate of
+/// the dependency graph rejects longer bodies for all the crates using `ruma-events`.
+#[cfg(feature = "limit-message-body-len")]
+pub const MAX_BODY_LEN: usize = 65_536;
+
 /// The content of an `m.room.message` event.
 ///
 /// This event is used when sending messages in a room.
@@ -106,6 +119,14 @@ impl RoomMessageEventContent {
         Self::new(MessageType::text_html(body, html_body))
     }
 
+    /// A constructor to create an html message from untrusted HTML.
+    ///
+    /// See [`TextMessageEventContent::from_html()`] for details.
+    #[cfg(feature = "html")]
+    pub fn text_from_html(html_body: impl AsRef<str>) -> Self {
+        Self::new(MessageType::text_from_html(html_body))
+    }
+
     /// A constructor to create a markdown message.
     #[cfg(feature = "markdown")]
     pub fn text_markdown(body: impl AsRef<str> + Into<String>) -> Self {
@@ -122,6 +143,14 @@ impl RoomMessageEventContent {
         Self::new(MessageType::notice_html(body, html_body))
     }
 
+    /// A constructor to create an html notice from untrusted HTML.
+    ///
+    /// See [`NoticeMessageEventContent::from_html()`] for details.
+    #[cfg(feature = "html")]
+    pub fn notice_from_html(html_body: impl AsRef<str>) -> Self {
+        Self::new(MessageType::notice_from_html(html_body))
+    }
+
     /// A constructor to create a markdown notice.
     #[cfg(feature = "markdown")]
     pub fn notice_markdown(body: impl AsRef<str> + Into<String>) -> Self {
@@ -138,12 +167,49 @@ impl RoomMessageEventContent {
         Self::new(MessageType::emote_html(body, html_body))
     }
 
+    /// A constructor to create an html emote from untrusted HTML.
+    ///
+    /// See [`EmoteMessageEventContent::from_html()`] for details.
+    #[cfg(feature = "html")]
+    pub fn emote_from_html(html_body: impl AsRef<str>) -> Self {
+        Self::new(MessageType::emote_from_html(html_body))
+    }
+
     /// A constructor to create a markdown emote.
     #[cfg(feature = "markdown")]
     pub fn emote_markdown(body: impl AsRef<str> + Into<String>) -> Self {
         Self::new(MessageType::emote_markdown(body))
     }
 
+    /// A constructor to create a message from the input of a message composer, interpreting slash
+    /// commands.
+    ///
+    /// The following commands are recognized:
+    ///
+    /// * `/me <text>` creates an emote.
+    /// * `/notice <text>` creates a notice.
+    /// * `/html <html>` creates a text message from untrusted HTML, if the `html` cargo feature is
+    ///   enabled, like `RoomMessageEventContent::text_from_html()`.
+    /// * `/plain <text>` creates a plain text message, even if `<text>` starts with a command.
+    ///
+    /// Any other input, including unknown commands, creates a plain text message with the whole
+    /// input.
+    pub fn from_slash_command(input: &str) -> Self {
+        let Some(command) = input.strip_prefix('/') else {
+            return Self::text_plain(input);
+        };
+        let (command, text) = command.split_once(' ').unwrap_or((command, ""));
+
+        match command {
+            "me" => Self::emote_plain(text),
+            "notice" => Self::notice_plain(text),
+            #[cfg(feature = "html")]
+            "html" => Self::text_from_html(text),
+            "plain" => Self::text_plain(text),
+            _ => Self::text_plain(input),
+        }
+    }
+
     /// Turns `self` into a reply to the given message.
     ///
     /// Takes the `body` / `formatted_body` (if any) in `self` for the main text and prepends a
@@ -242,6 +308,22 @@ impl RoomMessageEventContent {
         self
     }
 
+    /// Turns `self` into a new message for the thread with the given root event ID.
+    ///
+    /// Unlike [`Self::make_for_thread()`], this only needs the event IDs. The `m.in_reply_to`
+    /// field inside `relates_to` is set to `latest_event_id` as a fallback for clients that do not
+    /// support threads, so no reply fallback is added to the body.
+    ///
+    /// Any existing relation in `self` is replaced. In particular, calling this on a
+    /// [replacement] drops the `m.replace` relation and turns the edit into a new message, which
+    /// is most likely a mistake.
+    ///
+    /// [replacement]: https://spec.matrix.org/latest/client-server-api/#event-replacements
+    pub fn for_thread(mut self, thread_root: OwnedEventId, latest_event_id: OwnedEventId) -> Self {
+        self.relates_to = Some(Relation::Thread(Thread::plain(thread_root, latest_event_id)));
+        self
+    }
+
     /// Turns `self` into a [replacement] (or edit) for a given message.
     ///
     /// The first argument after `self` can be `&OriginalRoomMessageEvent` or
@@ -360,11 +442,76 @@ impl RoomMessageEventContent {
         self.msgtype.msgtype()
     }
 
+    /// Returns the kind of the `msgtype` of this message.
+    pub fn kind(&self) -> MessageTypeKind {
+        self.msgtype.kind()
+    }
+
     /// Return a reference to the message body.
     pub fn body(&self) -> &str {
         self.msgtype.body()
     }
 
+    /// Returns the data of the `msgtype`, if it is not known by Ruma.
+    ///
+    /// See [`MessageType::custom_data()`] for more details.
+    pub fn custom_data(&self) -> Option<&JsonObject> {
+        self.msgtype.custom_data()
+    }
+
+    /// Returns the ID of the event this message is a reply to, if any.
+    ///
+    /// This handles both regular replies and replies within a thread. The `m.in_reply_to` of a
+    /// thread relation that is only a fallback for clients that do not support threads is not a
+    /// genuine reply, so this returns `None` in that case.
+    pub fn in_reply_to(&self) -> Option<&EventId> {
+        match self.relates_to.as_ref()? {
+            Relation::Reply { in_reply_to } => Some(&in_reply_to.event_id),
+            Relation::Thread(Thread {
+                in_reply_to: Some(in_reply_to),
+                is_falling_back: false,
+                ..
+            }) => Some(&in_reply_to.event_id),
+            _ => None,
+        }
+    }
+
+    /// The length in bytes of the JSON serialization of this content.
+    ///
+    /// This can be used to reject messages that would exceed the size limit of events before
+    /// sending them. The content is serialized into a writer that only counts the bytes, so no
+    /// buffer is allocated.
+    pub fn estimated_serialized_len(&self) -> usize {
+        serialized_len(self).expect("room message content should serialize")
+    }
+
+    /// Whether the plain text body of this message is semantically the same as the one of the
+    /// given message.
+    ///
+    /// The [rich reply fallback] and the `* ` prefix of the fallback of [replacements] are
+    /// removed, and leading and trailing whitespace is ignored, before comparing the bodies. The
+    /// message types and the formatted bodies are not compared.
+    ///
+    /// [rich reply fallback]: https://spec.matrix.org/latest/client-server-api/#fallbacks-for-rich-replies
+    /// [replacements]: https://spec.matrix.org/latest/client-server-api/#event-replacements
+    pub fn body_eq(&self, other: &Self) -> bool {
+        self.normalized_body() == other.normalized_body()
+    }
+
+    /// The body of this message without fallbacks and surrounding whitespace.
+    fn normalized_body(&self) -> &str {
+        let body = match &self.relates_to {
+            Some(Relation::Reply { .. }) => remove_plain_reply_fallback(self.body()),
+            Some(Relation::Replacement(_)) => {
+                let body = remove_plain_reply_fallback(self.body());
+                body.strip_prefix("* ").unwrap_or(body)
+            }
+            _ => self.body(),
+        };
+
+        body.trim()
+    }
+
     /// Apply the given new content from a [`Replacement`] to this message.
     pub fn apply_replacement(&mut self, new_content: RoomMessageEventContentWithoutRelation) {
         let RoomMessageEventContentWithoutRelation { msgtype, mentions } = new_content;
@@ -502,6 +649,68 @@ pub enum MessageType {
     _Custom(CustomEventContent),
 }
 
+/// The kind of a [`MessageType`], without its content.
+///
+/// This can be used to match on the message type without comparing `msgtype` strings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum MessageTypeKind {
+    /// An audio message.
+    Audio,
+
+    /// An emote message.
+    Emote,
+
+    /// A file message.
+    File,
+
+    /// An image message.
+    Image,
+
+    /// A location message.
+    Location,
+
+    /// A notice message.
+    Notice,
+
+    /// A server notice message.
+    ServerNotice,
+
+    /// A text message.
+    Text,
+
+    /// A video message.
+    Video,
+
+    /// A request to initiate a key verification.
+    VerificationRequest,
+
+    /// A message type that is not known by Ruma.
+    Custom,
+}
+
+impl MessageTypeKind {
+    /// The kind of the message type with the given `msgtype` string.
+    ///
+    /// Returns [`MessageTypeKind::Custom`] for any `msgtype` that is not in
+    /// [`MessageType::ALL_KNOWN`].
+    pub fn from_msgtype(msgtype: &str) -> Self {
+        match msgtype {
+            "m.audio" => Self::Audio,
+            "m.emote" => Self::Emote,
+            "m.file" => Self::File,
+            "m.image" => Self::Image,
+            "m.location" => Self::Location,
+            "m.notice" => Self::Notice,
+            "m.server_notice" => Self::ServerNotice,
+            "m.text" => Self::Text,
+            "m.video" => Self::Video,
+            "m.key.verification.request" => Self::VerificationRequest,
+            _ => Self::Custom,
+        }
+    }
+}
+
 impl MessageType {
     /// Creates a new `MessageType`.
     ///
@@ -553,6 +762,14 @@ impl MessageType {
         Self::Text(TextMessageEventContent::html(body, html_body))
     }
 
+    /// A constructor to create an html message from untrusted HTML.
+    ///
+    /// See [`TextMessageEventContent::from_html()`] for details.
+    #[cfg(feature = "html")]
+    pub fn text_from_html(html_body: impl AsRef<str>) -> Self {
+        Self::Text(TextMessageEventContent::from_html(html_body))
+    }
+
     /// A constructor to create a markdown message.
     #[cfg(feature = "markdown")]
     pub fn text_markdown(body: impl AsRef<str> + Into<String>) -> Self {
@@ -569,6 +786,14 @@ impl MessageType {
         Self::Notice(NoticeMessageEventContent::html(body, html_body))
     }
 
+    /// A constructor to create an html notice from untrusted HTML.
+    ///
+    /// See [`NoticeMessageEventContent::from_html()`] for details.
+    #[cfg(feature = "html")]
+    pub fn notice_from_html(html_body: impl AsRef<str>) -> Self {
+        Self::Notice(NoticeMessageEventContent::from_html(html_body))
+    }
+
     /// A constructor to create a markdown notice.
     #[cfg(feature = "markdown")]
     pub fn notice_markdown(body: impl AsRef<str> + Into<String>) -> Self {
@@ -585,12 +810,36 @@ impl MessageType {
         Self::Emote(EmoteMessageEventContent::html(body, html_body))
     }
 
+    /// A constructor to create an html emote from untrusted HTML.
+    ///
+    /// See [`EmoteMessageEventContent::from_html()`] for details.
+    #[cfg(feature = "html")]
+    pub fn emote_from_html(html_body: impl AsRef<str>) -> Self {
+        Self::Emote(EmoteMessageEventContent::from_html(html_body))
+    }
+
     /// A constructor to create a markdown emote.
     #[cfg(feature = "markdown")]
     pub fn emote_markdown(body: impl AsRef<str> + Into<String>) -> Self {
         Self::Emote(EmoteMessageEventContent::markdown(body))
     }
 
+    /// The `msgtype` strings of the message types known by Ruma.
+    ///
+    /// Any other `msgtype` is deserialized as a custom message type.
+    pub const ALL_KNOWN: &'static [&'static str] = &[
+        "m.audio",
+        "m.emote",
+        "m.file",
+        "m.image",
+        "m.location",
+        "m.notice",
+        "m.server_notice",
+        "m.text",
+        "m.video",
+        "m.key.verification.request",
+    ];
+
     /// Returns a reference to the `msgtype` string.
     pub fn msgtype(&self) -> &str {
         match self {
@@ -608,6 +857,23 @@ impl MessageType {
         }
     }
 
+    /// Returns the kind of this message type.
+    pub fn kind(&self) -> MessageTypeKind {
+        match self {
+            Self::Audio(_) => MessageTypeKind::Audio,
+            Self::Emote(_) => MessageTypeKind::Emote,
+            Self::File(_) => MessageTypeKind::File,
+            Self::Image(_) => MessageTypeKind::Image,
+            Self::Location(_) => MessageTypeKind::Location,
+            Self::Notice(_) => MessageTypeKind::Notice,
+            Self::ServerNotice(_) => MessageTypeKind::ServerNotice,
+            Self::Text(_) => MessageTypeKind::Text,
+            Self::Video(_) => MessageTypeKind::Video,
+            Self::VerificationRequest(_) => MessageTypeKind::VerificationRequest,
+            Self::_Custom(_) => MessageTypeKind::Custom,
+        }
+    }
+
     /// Return a reference to the message body.
     pub fn body(&self) -> &str {
         match self {
@@ -658,6 +924,20 @@ impl MessageType {
         }
     }
 
+    /// Returns the associated data, if this is a message type that is not known by Ruma.
+    ///
+    /// The returned JSON object contains all the fields of the message type except `msgtype` and
+    /// `body`, exactly as they were deserialized, so they are preserved when serializing it again.
+    ///
+    /// Returns `None` for the message types that have a public variant in `MessageType`. Use
+    /// [`.data()`](Self::data) to get the data of any message type.
+    pub fn custom_data(&self) -> Option<&JsonObject> {
+        match self {
+            Self::_Custom(c) => Some(&c.data),
+            _ => None,
+        }
+    }
+
     /// Sanitize this message.
     ///
     /// If this message contains HTML, this removes the [tags and attributes] that are not listed in
@@ -785,6 +1065,43 @@ impl From<RoomMessageEventContent> for MessageType {
     }
 }
 
+macro_rules! impl_msgtype_conversions {
+    ($($variant:ident($content:ident)),* $(,)?) => {
+        $(
+            impl From<$content> for MessageType {
+                fn from(content: $content) -> Self {
+                    Self::$variant(content)
+                }
+            }
+
+            /// Returns the `MessageType` unchanged as the error if it is another variant.
+            impl TryFrom<MessageType> for $content {
+                type Error = MessageType;
+
+                fn try_from(msgtype: MessageType) -> Result<Self, Self::Error> {
+                    match msgtype {
+                        MessageType::$variant(content) => Ok(content),
+                        msgtype => Err(msgtype),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_msgtype_conversions!(
+    Audio(AudioMessageEventContent),
+    Emote(EmoteMessageEventContent),
+    File(FileMessageEventContent),
+    Image(ImageMessageEventContent),
+    Location(LocationMessageEventContent),
+    Notice(NoticeMessageEventContent),
+    ServerNotice(ServerNoticeMessageEventContent),
+    Text(TextMessageEventContent),
+    Video(VideoMessageEventContent),
+    VerificationRequest(KeyVerificationRequestEventContent),
+);
+
 /// Metadata about an event to be replaced.
 ///
 /// To be used with [`RoomMessageEventContent::make_replacement`].
@@ -799,17 +1116,30 @@ impl ReplacementMetadata {
     pub fn new(event_id: OwnedEventId, mentions: Option<Mentions>) -> Self {
         Self { event_id, mentions }
     }
+
+    /// Creates a `ReplacementMetadata` for the event with the given ID and content.
+    ///
+    /// If the content is itself a replacement, the original event is targeted instead, with the
+    /// mentions of its latest content, since edits of edits are not allowed.
+    fn from_event(event_id: &EventId, content: &RoomMessageEventContent) -> Self {
+        match &content.relates_to {
+            Some(Relation::Replacement(replacement)) => {
+                Self::new(replacement.event_id.clone(), replacement.new_content.mentions.clone())
+            }
+            _ => Self::new(event_id.to_owned(), content.mentions.clone()),
+        }
+    }
 }
 
 impl From<&OriginalRoomMessageEvent> for ReplacementMetadata {
     fn from(value: &OriginalRoomMessageEvent) -> Self {
-        ReplacementMetadata::new(value.event_id.to_owned(), value.content.mentions.clone())
+        Self::from_event(&value.event_id, &value.content)
     }
 }
 
 impl From<&OriginalSyncRoomMessageEvent> for ReplacementMetadata {
     fn from(value: &OriginalSyncRoomMessageEvent) -> Self {
-        ReplacementMetadata::new(value.event_id.to_owned(), value.content.mentions.clone())
+        Self::from_event(&value.event_id, &value.content)
     }
 }
 
@@ -1,11 +1,13 @@
 //! `Deserialize` implementation for RoomMessageEventContent and MessageType.
 
-use ruma_common::serde::from_raw_json_value;
+use std::borrow::Cow;
+
+use ruma_common::serde::{from_raw_json_value, from_raw_json_value_with_context};
 use serde::{de, Deserialize};
 use serde_json::value::RawValue as RawJsonValue;
 
 use super::{
-    relation_serde::deserialize_relation, MessageType, RoomMessageEventContent,
+    relation_serde::deserialize_relation, MessageType, MessageTypeKind, RoomMessageEventContent,
     RoomMessageEventContentWithoutRelation,
 };
 use crate::Mentions;
@@ -26,6 +28,40 @@ impl<'de> Deserialize<'de> for RoomMessageEventContent {
     }
 }
 
+impl RoomMessageEventContent {
+    /// Deserialize only the kind of the `msgtype` and the `body` of the given `m.room.message`
+    /// content.
+    ///
+    /// This is faster than deserializing the full content, for consumers that only need the plain
+    /// text body, like search indexes. The other fields are not validated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is not an object, or if the `msgtype` or `body` fields are
+    /// missing or are not strings.
+    pub fn deserialize_body_only(
+        raw: &RawJsonValue,
+    ) -> serde_json::Result<(MessageTypeKind, String)> {
+        let BodyOnlyDeHelper { msgtype, body } = serde_json::from_str(raw.get())?;
+
+        #[cfg(feature = "limit-message-body-len")]
+        check_body_len(&body)?;
+
+        let kind = MessageTypeKind::from_msgtype(&msgtype);
+
+        Ok((kind, body.into_owned()))
+    }
+}
+
+/// Helper struct to only deserialize the `msgtype` and `body` of a message.
+#[derive(Deserialize)]
+struct BodyOnlyDeHelper<'a> {
+    #[serde(borrow)]
+    msgtype: Cow<'a, str>,
+    #[serde(borrow)]
+    body: Cow<'a, str>,
+}
+
 impl<'de> Deserialize<'de> for RoomMessageEventContentWithoutRelation {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -60,22 +96,59 @@ impl<'de> Deserialize<'de> for MessageType {
         let json = Box::<RawJsonValue>::deserialize(deserializer)?;
         let MessageTypeDeHelper { msgtype } = from_raw_json_value(&json)?;
 
-        Ok(match msgtype.as_ref() {
-            "m.audio" => Self::Audio(from_raw_json_value(&json)?),
-            "m.emote" => Self::Emote(from_raw_json_value(&json)?),
-            "m.file" => Self::File(from_raw_json_value(&json)?),
-            "m.image" => Self::Image(from_raw_json_value(&json)?),
-            "m.location" => Self::Location(from_raw_json_value(&json)?),
-            "m.notice" => Self::Notice(from_raw_json_value(&json)?),
-            "m.server_notice" => Self::ServerNotice(from_raw_json_value(&json)?),
-            "m.text" => Self::Text(from_raw_json_value(&json)?),
-            "m.video" => Self::Video(from_raw_json_value(&json)?),
-            "m.key.verification.request" => Self::VerificationRequest(from_raw_json_value(&json)?),
-            _ => Self::_Custom(from_raw_json_value(&json)?),
-        })
+        // Check the length of the body borrowed from the raw JSON before building the content.
+        #[cfg(feature = "limit-message-body-len")]
+        {
+            let BodyLenDeHelper { body } = from_raw_json_value(&json)?;
+            check_body_len(body.as_deref().unwrap_or_default())?;
+        }
+
+        let msgtype = match msgtype.as_ref() {
+            "m.audio" => Self::Audio(from_raw_json_value_with_context(&json, &msgtype)?),
+            "m.emote" => Self::Emote(from_raw_json_value_with_context(&json, &msgtype)?),
+            "m.file" => Self::File(from_raw_json_value_with_context(&json, &msgtype)?),
+            "m.image" => Self::Image(from_raw_json_value_with_context(&json, &msgtype)?),
+            "m.location" => Self::Location(from_raw_json_value_with_context(&json, &msgtype)?),
+            "m.notice" => Self::Notice(from_raw_json_value_with_context(&json, &msgtype)?),
+            "m.server_notice" => {
+                Self::ServerNotice(from_raw_json_value_with_context(&json, &msgtype)?)
+            }
+            "m.text" => Self::Text(from_raw_json_value_with_context(&json, &msgtype)?),
+            "m.video" => Self::Video(from_raw_json_value_with_context(&json, &msgtype)?),
+            "m.key.verification.request" => {
+                Self::VerificationRequest(from_raw_json_value_with_context(&json, &msgtype)?)
+            }
+            _ => Self::_Custom(from_raw_json_value_with_context(&json, &msgtype)?),
+        };
+
+        Ok(msgtype)
     }
 }
 
+/// Helper struct to only deserialize the `body` of a message, borrowing it if possible.
+#[cfg(feature = "limit-message-body-len")]
+#[derive(Deserialize)]
+struct BodyLenDeHelper<'a> {
+    /// The body of the message.
+    ///
+    /// A missing `body` is reported by the deserialization of the message type.
+    #[serde(borrow)]
+    body: Option<Cow<'a, str>>,
+}
+
+/// Checks that the given `body` is not longer than [`MAX_BODY_LEN`](super::MAX_BODY_LEN).
+#[cfg(feature = "limit-message-body-len")]
+fn check_body_len<E: de::Error>(body: &str) -> Result<(), E> {
+    if body.len() > super::MAX_BODY_LEN {
+        return Err(E::custom(format!(
+            "body is longer than the maximum of {} bytes",
+            super::MAX_BODY_LEN
+        )));
+    }
+
+    Ok(())
+}
+
 #[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/112615
 #[cfg(feature = "unstable-msc3488")]
 pub(in super::super) mod msc3488 {
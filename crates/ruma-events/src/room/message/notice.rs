@@ -1,3 +1,5 @@
+#[cfg(feature = "html")]
+use ruma_html::{Html, SanitizerConfig};
 use serde::{Deserialize, Serialize};
 
 use super::FormattedBody;
@@ -28,6 +30,17 @@ impl NoticeMessageEventContent {
         Self { body, formatted: Some(FormattedBody::html(html_body)) }
     }
 
+    /// A convenience constructor to create an html notice from untrusted HTML.
+    ///
+    /// See [`TextMessageEventContent::from_html()`](super::TextMessageEventContent::from_html)
+    /// for details.
+    #[cfg(feature = "html")]
+    pub fn from_html(html_body: impl AsRef<str>) -> Self {
+        let mut html = Html::parse(html_body.as_ref());
+        html.sanitize_with(SanitizerConfig::compat());
+        Self::html(html.to_plain_text(), html.to_string())
+    }
+
     /// A convenience constructor to create a markdown notice.
     ///
     /// Returns an html notice if some markdown formatting was detected, otherwise returns a plain
@@ -1,6 +1,6 @@
 use std::borrow::Cow;
 
-use ruma_common::{serde::JsonObject, EventId};
+use ruma_common::{serde::JsonObject, EventId, OwnedEventId};
 
 use crate::relation::{CustomRelation, InReplyTo, RelationType, Replacement, Thread};
 
@@ -26,6 +26,32 @@ pub enum Relation<C> {
 }
 
 impl<C> Relation<C> {
+    /// Creates a `Thread` relation with the given thread root event ID and *without* the
+    /// recommended reply fallback.
+    ///
+    /// Use [`Relation::thread_plain()`] to include a reply fallback for clients that do not
+    /// support threads.
+    pub fn thread(thread_root: OwnedEventId) -> Self {
+        Self::Thread(Thread::without_fallback(thread_root))
+    }
+
+    /// Creates a `Thread` relation with the given thread root event ID and latest message-like
+    /// event ID in the thread.
+    ///
+    /// The latest event is used as a reply fallback for clients that do not support threads, so
+    /// `is_falling_back` is set to `true`.
+    pub fn thread_plain(thread_root: OwnedEventId, latest_event_id: OwnedEventId) -> Self {
+        Self::Thread(Thread::plain(thread_root, latest_event_id))
+    }
+
+    /// Creates a `Thread` relation for a reply within a thread, with the given thread root event
+    /// ID and replied-to event ID.
+    ///
+    /// This is a genuine reply, so `is_falling_back` is set to `false`.
+    pub fn thread_reply(thread_root: OwnedEventId, reply_to_event_id: OwnedEventId) -> Self {
+        Self::Thread(Thread::reply(thread_root, reply_to_event_id))
+    }
+
     /// The type of this `Relation`.
     ///
     /// Returns an `Option` because the `Reply` relation does not have a`rel_type` field.
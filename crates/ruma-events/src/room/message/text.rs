@@ -1,3 +1,5 @@
+#[cfg(feature = "html")]
+use ruma_html::{Html, SanitizerConfig};
 use serde::{Deserialize, Serialize};
 
 use super::FormattedBody;
@@ -28,6 +30,20 @@ impl TextMessageEventContent {
         Self { body, formatted: Some(FormattedBody::html(html_body)) }
     }
 
+    /// A convenience constructor to create an HTML message from untrusted HTML.
+    ///
+    /// The HTML is sanitized by keeping only the tags and attributes allowed in
+    /// [`HtmlSanitizerMode::Compat`] mode, and the plain text `body` is derived from the text
+    /// content of the sanitized HTML.
+    ///
+    /// [`HtmlSanitizerMode::Compat`]: ruma_html::HtmlSanitizerMode::Compat
+    #[cfg(feature = "html")]
+    pub fn from_html(html_body: impl AsRef<str>) -> Self {
+        let mut html = Html::parse(html_body.as_ref());
+        html.sanitize_with(SanitizerConfig::compat());
+        Self::html(html.to_plain_text(), html.to_string())
+    }
+
     /// A convenience constructor to create a Markdown message.
     ///
     /// Returns an HTML message if some Markdown formatting was detected, otherwise returns a plain
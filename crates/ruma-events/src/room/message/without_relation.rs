@@ -49,6 +49,15 @@ impl RoomMessageEventContentWithoutRelation {
         Self::new(MessageType::text_html(body, html_body))
     }
 
+    /// A constructor to create an html message from untrusted HTML.
+    ///
+    /// See [`TextMessageEventContent::from_html()`](super::TextMessageEventContent::from_html) for
+    /// details.
+    #[cfg(feature = "html")]
+    pub fn text_from_html(html_body: impl AsRef<str>) -> Self {
+        Self::new(MessageType::text_from_html(html_body))
+    }
+
     /// A constructor to create a markdown message.
     #[cfg(feature = "markdown")]
     pub fn text_markdown(body: impl AsRef<str> + Into<String>) -> Self {
@@ -65,6 +74,15 @@ impl RoomMessageEventContentWithoutRelation {
         Self::new(MessageType::notice_html(body, html_body))
     }
 
+    /// A constructor to create an html notice from untrusted HTML.
+    ///
+    /// See [`NoticeMessageEventContent::from_html()`](super::NoticeMessageEventContent::from_html)
+    /// for details.
+    #[cfg(feature = "html")]
+    pub fn notice_from_html(html_body: impl AsRef<str>) -> Self {
+        Self::new(MessageType::notice_from_html(html_body))
+    }
+
     /// A constructor to create a markdown notice.
     #[cfg(feature = "markdown")]
     pub fn notice_markdown(body: impl AsRef<str> + Into<String>) -> Self {
@@ -81,6 +99,15 @@ impl RoomMessageEventContentWithoutRelation {
         Self::new(MessageType::emote_html(body, html_body))
     }
 
+    /// A constructor to create an html emote from untrusted HTML.
+    ///
+    /// See [`EmoteMessageEventContent::from_html()`](super::EmoteMessageEventContent::from_html)
+    /// for details.
+    #[cfg(feature = "html")]
+    pub fn emote_from_html(html_body: impl AsRef<str>) -> Self {
+        Self::new(MessageType::emote_from_html(html_body))
+    }
+
     /// A constructor to create a markdown emote.
     #[cfg(feature = "markdown")]
     pub fn emote_markdown(body: impl AsRef<str> + Into<String>) -> Self {
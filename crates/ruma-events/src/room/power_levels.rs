@@ -14,7 +14,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     EmptyStateKey, EventContent, MessageLikeEventType, RedactContent, RedactedStateEventContent,
-    StateEventType, StaticEventContent, TimelineEventType,
+    RedactionAllowedKeys, StateEventType, StaticEventContent, TimelineEventType,
 };
 
 /// The content of an `m.room.power_levels` event.
@@ -178,6 +178,8 @@ impl RedactContent for RoomPowerLevelsEventContent {
     }
 }
 
+impl RedactionAllowedKeys for RoomPowerLevelsEventContent {}
+
 /// Used with `#[serde(skip_serializing_if)]` to omit default power levels.
 #[allow(clippy::trivially_copy_pass_by_ref)]
 fn is_default_power_level(l: &Int) -> bool {
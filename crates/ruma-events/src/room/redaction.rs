@@ -16,7 +16,7 @@ use tracing::error;
 
 use crate::{
     BundledMessageLikeRelations, EventContent, MessageLikeEventType, RedactContent,
-    RedactedMessageLikeEventContent, RedactedUnsigned, StaticEventContent,
+    RedactedMessageLikeEventContent, RedactedUnsigned, RedactionAllowedKeys, StaticEventContent,
 };
 
 mod event_serde;
@@ -233,6 +233,8 @@ impl RedactContent for RoomRedactionEventContent {
     }
 }
 
+impl RedactionAllowedKeys for RoomRedactionEventContent {}
+
 /// A redacted redaction event.
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
@@ -2,9 +2,14 @@
 //!
 //! [`m.room.third_party_invite`]: https://spec.matrix.org/latest/client-server-api/#mroomthird_party_invite
 
-use ruma_common::serde::Base64;
+use std::{borrow::Cow, collections::BTreeMap};
+
+use ruma_common::{serde::Base64, OwnedUserId};
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use super::member::{MembershipState, RoomMemberEventContent, SignedContent, ThirdPartyInvite};
 
 /// The content of an `m.room.third_party_invite` event.
 ///
@@ -44,11 +49,150 @@ pub struct RoomThirdPartyInviteEventContent {
 }
 
 impl RoomThirdPartyInviteEventContent {
+    /// The maximum number of characters of the display name returned by
+    /// [`Self::sanitized_display_name()`].
+    pub const MAX_SANITIZED_DISPLAY_NAME_LEN: usize = 256;
+
     /// Creates a new `RoomThirdPartyInviteEventContent` with the given display name, key validity
     /// url and public key.
     pub fn new(display_name: String, key_validity_url: String, public_key: Base64) -> Self {
         Self { display_name, key_validity_url, public_key, public_keys: None }
     }
+
+    /// The display name of the invited user, safe to be displayed.
+    ///
+    /// Control characters are removed and the result is truncated to
+    /// [`Self::MAX_SANITIZED_DISPLAY_NAME_LEN`] characters. The `display_name` field is left
+    /// untouched, so it can still be used for signature verification.
+    pub fn sanitized_display_name(&self) -> Cow<'_, str> {
+        let is_clean = !self.display_name.chars().any(char::is_control)
+            && self.display_name.chars().count() <= Self::MAX_SANITIZED_DISPLAY_NAME_LEN;
+
+        if is_clean {
+            Cow::Borrowed(&self.display_name)
+        } else {
+            Cow::Owned(
+                self.display_name
+                    .chars()
+                    .filter(|c| !c.is_control())
+                    .take(Self::MAX_SANITIZED_DISPLAY_NAME_LEN)
+                    .collect(),
+            )
+        }
+    }
+
+    /// Creates the content of the `m.room.member` invite event that replaces this third-party
+    /// invite, once it was claimed by the user with the given ID.
+    ///
+    /// `state_key_token` is the state key of this event. The `signatures` of the returned
+    /// [`SignedContent`] are empty and must be filled with the signatures received from the
+    /// identity server.
+    pub fn to_member_invite(
+        &self,
+        state_key_token: &str,
+        mxid: OwnedUserId,
+    ) -> RoomMemberEventContent {
+        let signed = SignedContent::new(BTreeMap::new(), mxid, state_key_token.to_owned());
+
+        let mut content = RoomMemberEventContent::new(MembershipState::Invite);
+        content.third_party_invite = Some(ThirdPartyInvite::new(self.display_name.clone(), signed));
+        content
+    }
+
+    /// The keys with which the token may be signed, reconciling the legacy `public_key` and
+    /// `key_validity_url` fields with `public_keys`.
+    ///
+    /// If `public_keys` contains keys, they are returned and the legacy fields are ignored. A
+    /// warning is logged if `public_key` doesn't match the first entry of `public_keys`, as
+    /// required by the spec. Otherwise, a key is built from the legacy fields, unless
+    /// `public_key` is empty.
+    pub fn canonical_keys(&self) -> Vec<PublicKey> {
+        if let Some(public_keys) = self.public_keys.as_ref().filter(|keys| !keys.is_empty()) {
+            if !self.public_key.as_bytes().is_empty()
+                && self.public_key != public_keys[0].public_key
+            {
+                warn!(
+                    "public_key of m.room.third_party_invite doesn't match the first entry of \
+                     public_keys, ignoring it"
+                );
+            }
+
+            return public_keys.clone();
+        }
+
+        if self.public_key.as_bytes().is_empty() {
+            return Vec::new();
+        }
+
+        let key_validity_url =
+            (!self.key_validity_url.is_empty()).then(|| self.key_validity_url.clone());
+        vec![PublicKey { key_validity_url, public_key: self.public_key.clone() }]
+    }
+
+    /// Checks that the keys of this invite can be used to sign the token.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `public_key` or an entry of `public_keys` is empty, or if
+    /// `public_keys` is present but contains no keys.
+    pub fn validate(&self) -> Result<(), ThirdPartyInviteError> {
+        if self.public_key.as_bytes().is_empty() {
+            return Err(ThirdPartyInviteError::EmptyPublicKey);
+        }
+
+        if let Some(public_keys) = &self.public_keys {
+            if public_keys.is_empty() {
+                return Err(ThirdPartyInviteError::NoPublicKeys);
+            }
+
+            if let Some(index) =
+                public_keys.iter().position(|key| key.public_key.as_bytes().is_empty())
+            {
+                return Err(ThirdPartyInviteError::EmptyPublicKeysEntry(index));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// An error encountered when validating a [`RoomThirdPartyInviteEventContent`].
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum ThirdPartyInviteError {
+    /// The `public_key` field is empty.
+    #[error("public_key is empty")]
+    EmptyPublicKey,
+    /// The `public_keys` field is present but doesn't contain any key.
+    #[error("public_keys doesn't contain any key")]
+    NoPublicKeys,
+    /// The entry at the given index of `public_keys` has an empty key.
+    #[error("the key at index {0} of public_keys is empty")]
+    EmptyPublicKeysEntry(usize),
+}
+
+/// Checks that the given `state_key` of an `m.room.third_party_invite` event is a valid invite
+/// token.
+///
+/// # Errors
+///
+/// Returns an error if the token is empty.
+pub fn validate_token(state_key: &str) -> Result<(), TokenError> {
+    if state_key.is_empty() {
+        return Err(TokenError::Empty);
+    }
+
+    Ok(())
+}
+
+/// An error encountered when validating the token of a third-party invite with
+/// [`validate_token()`].
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum TokenError {
+    /// The token is empty.
+    #[error("third-party invite token is empty")]
+    Empty,
 }
 
 /// A public key for signing a third party invite token.
@@ -72,3 +216,116 @@ impl PublicKey {
         Self { key_validity_url: None, public_key }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use assert_matches2::assert_matches;
+    use ruma_common::{owned_user_id, serde::Base64};
+
+    use super::{
+        validate_token, PublicKey, RoomThirdPartyInviteEventContent, ThirdPartyInviteError,
+        TokenError,
+    };
+    use crate::room::member::MembershipState;
+
+    #[test]
+    fn sanitized_display_name() {
+        let mut content = RoomThirdPartyInviteEventContent::new(
+            "Alice".to_owned(),
+            "http://identity.local/_matrix/identity/v2/pubkey".to_owned(),
+            Base64::new(vec![0; 32]),
+        );
+        assert_matches!(content.sanitized_display_name(), Cow::Borrowed("Alice"));
+
+        content.display_name = "Al\u{1b}[31mice\n".to_owned();
+        assert_eq!(content.sanitized_display_name(), "Al[31mice");
+
+        content.display_name = "a".repeat(300);
+        assert_eq!(
+            content.sanitized_display_name().len(),
+            RoomThirdPartyInviteEventContent::MAX_SANITIZED_DISPLAY_NAME_LEN
+        );
+        assert_eq!(content.display_name.len(), 300);
+    }
+
+    #[test]
+    fn to_member_invite() {
+        let content = RoomThirdPartyInviteEventContent::new(
+            "Alice".to_owned(),
+            "http://identity.local/_matrix/identity/v2/pubkey".to_owned(),
+            Base64::new(vec![0; 32]),
+        );
+
+        let member = content.to_member_invite("abc123", owned_user_id!("@alice:localhost"));
+        assert_eq!(member.membership, MembershipState::Invite);
+
+        let invite = member.third_party_invite.unwrap();
+        assert_eq!(invite.display_name, "Alice");
+        assert_eq!(invite.signed.mxid, "@alice:localhost");
+        assert_eq!(invite.signed.token, "abc123");
+        assert!(invite.signed.signatures.is_empty());
+    }
+
+    #[test]
+    fn validate() {
+        let mut content = RoomThirdPartyInviteEventContent::new(
+            "Alice".to_owned(),
+            "http://identity.local/_matrix/identity/v2/pubkey".to_owned(),
+            Base64::new(vec![0; 32]),
+        );
+        content.validate().unwrap();
+
+        content.public_keys = Some(vec![]);
+        assert_eq!(content.validate(), Err(ThirdPartyInviteError::NoPublicKeys));
+
+        content.public_keys =
+            Some(vec![PublicKey::new(Base64::new(vec![1; 32])), PublicKey::new(Base64::empty())]);
+        assert_eq!(content.validate(), Err(ThirdPartyInviteError::EmptyPublicKeysEntry(1)));
+
+        content.public_keys = Some(vec![PublicKey::new(Base64::new(vec![1; 32]))]);
+        content.validate().unwrap();
+
+        content.public_key = Base64::empty();
+        assert_eq!(content.validate(), Err(ThirdPartyInviteError::EmptyPublicKey));
+    }
+
+    #[test]
+    fn canonical_keys() {
+        let mut content = RoomThirdPartyInviteEventContent::new(
+            "Alice".to_owned(),
+            "http://identity.local/_matrix/identity/v2/pubkey".to_owned(),
+            Base64::new(vec![0; 32]),
+        );
+        let keys = content.canonical_keys();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(
+            keys[0].key_validity_url.as_deref(),
+            Some("http://identity.local/_matrix/identity/v2/pubkey")
+        );
+        assert_eq!(keys[0].public_key, Base64::new(vec![0; 32]));
+
+        content.public_keys = Some(vec![
+            PublicKey::new(Base64::new(vec![1; 32])),
+            PublicKey::new(Base64::new(vec![2; 32])),
+        ]);
+        let keys = content.canonical_keys();
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0].key_validity_url, None);
+        assert_eq!(keys[0].public_key, Base64::new(vec![1; 32]));
+        assert_eq!(keys[1].public_key, Base64::new(vec![2; 32]));
+
+        content.public_keys = Some(vec![]);
+        assert_eq!(content.canonical_keys().len(), 1);
+
+        content.public_key = Base64::empty();
+        assert_eq!(content.canonical_keys().len(), 0);
+    }
+
+    #[test]
+    fn token_validation() {
+        validate_token("abc123").unwrap();
+        assert_eq!(validate_token(""), Err(TokenError::Empty));
+    }
+}
@@ -2,13 +2,15 @@
 //!
 //! [`m.room.tombstone`]: https://spec.matrix.org/latest/client-server-api/#mroomtombstone
 
-use ruma_common::OwnedRoomId;
+#[cfg(feature = "unstable-unspecified")]
+use ruma_common::OwnedServerName;
+use ruma_common::{OwnedRoomId, RoomId};
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    EmptyStateKey, EventContent, PossiblyRedactedStateEventContent, StateEventType,
-    StaticEventContent,
+    EmptyStateKey, EventContent, PossiblyRedactedStateEventContent, RedactionAllowedKeys,
+    StateEventType, StaticEventContent,
 };
 
 /// The content of an `m.room.tombstone` event.
@@ -32,13 +34,59 @@ pub struct RoomTombstoneEventContent {
     pub body: String,
 
     /// The new room the client should be visiting.
+    #[cfg(not(feature = "unstable-unspecified"))]
     pub replacement_room: OwnedRoomId,
+
+    /// The new room the client should be visiting.
+    ///
+    /// This is `None` if the room was closed without a successor.
+    #[cfg(feature = "unstable-unspecified")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replacement_room: Option<OwnedRoomId>,
+
+    /// List of candidate servers that can be used to join the replacement room.
+    #[cfg(feature = "unstable-unspecified")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub via: Vec<OwnedServerName>,
 }
 
 impl RoomTombstoneEventContent {
     /// Creates a new `RoomTombstoneEventContent` with the given body and replacement room ID.
     pub fn new(body: String, replacement_room: OwnedRoomId) -> Self {
-        Self { body, replacement_room }
+        Self {
+            body,
+            #[cfg(not(feature = "unstable-unspecified"))]
+            replacement_room,
+            #[cfg(feature = "unstable-unspecified")]
+            replacement_room: Some(replacement_room),
+            #[cfg(feature = "unstable-unspecified")]
+            via: Vec::new(),
+        }
+    }
+
+    /// Creates a new `RoomTombstoneEventContent` with the given body, that closes the room without
+    /// a replacement room.
+    #[cfg(feature = "unstable-unspecified")]
+    pub fn close(body: String) -> Self {
+        Self { body, replacement_room: None, via: Vec::new() }
+    }
+
+    /// The candidate servers that can be used to join the replacement room.
+    #[cfg(feature = "unstable-unspecified")]
+    pub fn replacement_via(&self) -> &[OwnedServerName] {
+        &self.via
+    }
+
+    /// Whether the replacement room of this tombstone is the given room.
+    ///
+    /// This can be used to avoid following a tombstone to a room the client is already in.
+    pub fn points_to(&self, room: &RoomId) -> bool {
+        #[cfg(not(feature = "unstable-unspecified"))]
+        let replacement_room = Some(&self.replacement_room);
+        #[cfg(feature = "unstable-unspecified")]
+        let replacement_room = self.replacement_room.as_ref();
+
+        replacement_room.is_some_and(|replacement_room| replacement_room == room)
     }
 }
 
@@ -53,6 +101,11 @@ pub struct PossiblyRedactedRoomTombstoneEventContent {
 
     /// The new room the client should be visiting.
     pub replacement_room: Option<OwnedRoomId>,
+
+    /// List of candidate servers that can be used to join the replacement room.
+    #[cfg(feature = "unstable-unspecified")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub via: Vec<OwnedServerName>,
 }
 
 impl EventContent for PossiblyRedactedRoomTombstoneEventContent {
@@ -70,3 +123,112 @@ impl PossiblyRedactedStateEventContent for PossiblyRedactedRoomTombstoneEventCon
 impl StaticEventContent for PossiblyRedactedRoomTombstoneEventContent {
     const TYPE: &'static str = "m.room.tombstone";
 }
+
+impl RedactionAllowedKeys for RoomTombstoneEventContent {}
+
+impl RedactionAllowedKeys for PossiblyRedactedRoomTombstoneEventContent {}
+
+#[cfg(test)]
+mod tests {
+    use ruma_common::{owned_room_id, room_id, RoomVersionId};
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::{PossiblyRedactedRoomTombstoneEventContent, RoomTombstoneEventContent};
+    use crate::redact_possibly;
+
+    #[test]
+    fn serialization_with_replacement_room() {
+        let content = RoomTombstoneEventContent::new(
+            "Room upgraded".to_owned(),
+            owned_room_id!("!newroom:localhost"),
+        );
+        let json = json!({
+            "body": "Room upgraded",
+            "replacement_room": "!newroom:localhost",
+        });
+
+        assert_eq!(to_json_value(content).unwrap(), json);
+
+        let content = from_json_value::<RoomTombstoneEventContent>(json).unwrap();
+        assert_eq!(content.body, "Room upgraded");
+    }
+
+    #[test]
+    fn points_to() {
+        let content = RoomTombstoneEventContent::new(
+            "Room upgraded".to_owned(),
+            owned_room_id!("!newroom:localhost"),
+        );
+
+        assert!(content.points_to(room_id!("!newroom:localhost")));
+        assert!(!content.points_to(room_id!("!oldroom:localhost")));
+    }
+
+    #[test]
+    fn redact_possibly_redacted() {
+        let content = from_json_value::<PossiblyRedactedRoomTombstoneEventContent>(json!({
+            "body": "Room upgraded",
+            "replacement_room": "!newroom:localhost",
+        }))
+        .unwrap();
+
+        let redacted = redact_possibly(content, &RoomVersionId::V11).unwrap();
+        assert_eq!(redacted.body, None);
+        assert_eq!(redacted.replacement_room, None);
+    }
+
+    #[test]
+    #[cfg(feature = "unstable-unspecified")]
+    fn close_without_replacement_room() {
+        let content = RoomTombstoneEventContent::close("Room closed".to_owned());
+        let json = json!({ "body": "Room closed" });
+
+        assert_eq!(to_json_value(content).unwrap(), json);
+
+        let content = from_json_value::<RoomTombstoneEventContent>(json).unwrap();
+        assert_eq!(content.body, "Room closed");
+        assert_eq!(content.replacement_room, None);
+    }
+
+    #[test]
+    #[cfg(feature = "unstable-unspecified")]
+    fn serialization_with_via() {
+        use ruma_common::owned_server_name;
+
+        let mut content = RoomTombstoneEventContent::new(
+            "Room upgraded".to_owned(),
+            owned_room_id!("!newroom:localhost"),
+        );
+        content.via = vec![owned_server_name!("localhost")];
+        let json = json!({
+            "body": "Room upgraded",
+            "replacement_room": "!newroom:localhost",
+            "via": ["localhost"],
+        });
+
+        assert_eq!(to_json_value(content).unwrap(), json);
+
+        let content = from_json_value::<RoomTombstoneEventContent>(json).unwrap();
+        assert_eq!(content.replacement_via(), [owned_server_name!("localhost")]);
+    }
+
+    #[test]
+    #[cfg(feature = "unstable-unspecified")]
+    fn possibly_redacted_serialization_with_via() {
+        use ruma_common::owned_server_name;
+
+        let json = json!({
+            "body": "Room upgraded",
+            "replacement_room": "!newroom:localhost",
+            "via": ["localhost"],
+        });
+
+        let content =
+            from_json_value::<PossiblyRedactedRoomTombstoneEventContent>(json.clone()).unwrap();
+        assert_eq!(content.via, [owned_server_name!("localhost")]);
+        assert_eq!(to_json_value(&content).unwrap(), json);
+
+        let redacted = redact_possibly(content, &RoomVersionId::V11).unwrap();
+        assert!(redacted.via.is_empty());
+    }
+}
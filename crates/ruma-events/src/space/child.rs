@@ -2,9 +2,20 @@
 //!
 //! [`m.space.child`]: https://spec.matrix.org/latest/client-server-api/#mspacechild
 
-use ruma_common::{MilliSecondsSinceUnixEpoch, OwnedRoomId, OwnedServerName, OwnedUserId};
+use std::cmp::Ordering;
+
+use ruma_common::{
+    serde::Raw, IdParseError, MilliSecondsSinceUnixEpoch, OwnedRoomId, OwnedServerName,
+    OwnedUserId, RoomId,
+};
 use ruma_macros::{Event, EventContent};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value as JsonValue;
+
+use crate::{
+    AnyStrippedStateEvent, EventContent, PossiblyRedactedStateEventContent, StateEventContent,
+    StateEventType, StaticEventContent,
+};
 
 /// The content of an `m.space.child` event.
 ///
@@ -15,9 +26,22 @@ use serde::{Deserialize, Serialize};
 /// which gives a list of candidate servers that can be used to join the room.
 #[derive(Clone, Debug, Deserialize, Serialize, EventContent)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
-#[ruma_event(type = "m.space.child", kind = State, state_key_type = OwnedRoomId)]
+#[ruma_event(
+    type = "m.space.child",
+    kind = State,
+    state_key_type = OwnedRoomId,
+    custom_possibly_redacted
+)]
 pub struct SpaceChildEventContent {
     /// List of candidate servers that can be used to join the room.
+    ///
+    /// If you activate the `compat-lenient-space-child-via` feature, invalid server names in this
+    /// list are skipped during deserialization instead of making it fail. Use
+    /// [`SpaceChildEventContent::invalid_via_count()`] to know how many were skipped.
+    #[cfg_attr(
+        feature = "compat-lenient-space-child-via",
+        serde(deserialize_with = "deserialize_via_lenient")
+    )]
     pub via: Vec<OwnedServerName>,
 
     /// Provide a default ordering of siblings in the room list.
@@ -29,8 +53,14 @@ pub struct SpaceChildEventContent {
     /// not consist solely of ascii characters in the range `\x20` (space) to `\x7E` (`~`), or
     /// consist of more than 50 characters, are forbidden and the field should be ignored if
     /// received.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub order: Option<String>,
+    ///
+    /// Invalid `order`s are deserialized as `None`.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_order",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub order: Option<SpaceChildOrder>,
 
     /// Space admins can mark particular children of a space as "suggested".
     ///
@@ -49,6 +79,242 @@ impl SpaceChildEventContent {
     pub fn new(via: Vec<OwnedServerName>) -> Self {
         Self { via, order: None, suggested: false }
     }
+
+    /// The number of entries of `via` in the given raw content that are not valid server names.
+    ///
+    /// These entries are skipped when deserializing the content.
+    #[cfg(feature = "compat-lenient-space-child-via")]
+    pub fn invalid_via_count(raw: &Raw<Self>) -> usize {
+        raw.get_field::<Vec<JsonValue>>("via")
+            .ok()
+            .flatten()
+            .map_or(0, |via| via.into_iter().filter(|v| parse_via_entry(v).is_none()).count())
+    }
+
+    /// Checks that this content is consistent.
+    ///
+    /// This doesn't prevent the content from being deserialized, but can be used by tooling to
+    /// flag contradictory contents.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the child is marked as `suggested` but `via` is empty, since it can't be
+    /// joined.
+    pub fn validate(&self) -> Result<(), SpaceChildError> {
+        if self.suggested && self.via.is_empty() {
+            return Err(SpaceChildError::SuggestedWithoutVia);
+        }
+
+        Ok(())
+    }
+
+    /// Returns up to `limit` servers of `via` to use to join the given child room.
+    ///
+    /// If the server of the child room's ID is in `via`, it is returned first, since it is likely
+    /// to still be in the room. The other servers keep their order in `via`, and duplicates are
+    /// removed.
+    pub fn best_via(&self, child: &RoomId, limit: usize) -> Vec<OwnedServerName> {
+        let child_server =
+            child.server_name().filter(|server| self.via.iter().any(|via| via == server));
+
+        let mut servers = Vec::with_capacity(limit.min(self.via.len()));
+        for server in child_server.into_iter().chain(self.via.iter().map(|via| &**via)) {
+            if servers.len() == limit {
+                break;
+            }
+
+            if !servers.iter().any(|s: &OwnedServerName| s == server) {
+                servers.push(server.to_owned());
+            }
+        }
+
+        servers
+    }
+
+    /// Converts `self` into a [`HierarchySpaceChildEvent`] for the given child room, sent by the
+    /// given user at the given time.
+    pub fn into_child_event(
+        self,
+        child: OwnedRoomId,
+        sender: OwnedUserId,
+        origin_server_ts: MilliSecondsSinceUnixEpoch,
+    ) -> HierarchySpaceChildEvent {
+        HierarchySpaceChildEvent {
+            content: self,
+            sender,
+            state_key: child.into(),
+            origin_server_ts,
+        }
+    }
+}
+
+/// An inconsistency found when validating a [`SpaceChildEventContent`].
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum SpaceChildError {
+    /// The child is marked as `suggested`, but `via` is empty.
+    #[error("suggested space child has no via servers")]
+    SuggestedWithoutVia,
+}
+
+/// A valid `order` of a space child.
+///
+/// It consists of at most [`SpaceChildOrder::MAX_LEN`] ASCII characters in the range `\x20`
+/// (space) to `\x7E` (`~`). Its `Ord` implementation is the lexicographic ordering used to sort
+/// space children.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct SpaceChildOrder(String);
+
+impl SpaceChildOrder {
+    /// The maximum number of characters of a valid `order`.
+    pub const MAX_LEN: usize = 50;
+
+    /// The `order` as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    fn validate(order: &str) -> Result<(), SpaceChildOrderError> {
+        if order.len() > Self::MAX_LEN {
+            Err(SpaceChildOrderError::TooLong)
+        } else if !order.bytes().all(|b| (0x20..=0x7E).contains(&b)) {
+            Err(SpaceChildOrderError::InvalidCharacter)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl TryFrom<String> for SpaceChildOrder {
+    type Error = SpaceChildOrderError;
+
+    fn try_from(order: String) -> Result<Self, Self::Error> {
+        Self::validate(&order)?;
+        Ok(Self(order))
+    }
+}
+
+impl TryFrom<&str> for SpaceChildOrder {
+    type Error = SpaceChildOrderError;
+
+    fn try_from(order: &str) -> Result<Self, Self::Error> {
+        Self::validate(order)?;
+        Ok(Self(order.to_owned()))
+    }
+}
+
+impl AsRef<str> for SpaceChildOrder {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<SpaceChildOrder> for String {
+    fn from(order: SpaceChildOrder) -> Self {
+        order.0
+    }
+}
+
+/// An error encountered when trying to convert a string to a [`SpaceChildOrder`].
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum SpaceChildOrderError {
+    /// The string is longer than [`SpaceChildOrder::MAX_LEN`].
+    #[error("order is longer than {} characters", SpaceChildOrder::MAX_LEN)]
+    TooLong,
+    /// The string contains a character outside of the range `\x20` to `\x7E`.
+    #[error("order contains a character outside of the printable ASCII range")]
+    InvalidCharacter,
+}
+
+/// Deserializes an `order`, ignoring invalid values as required by the spec.
+fn deserialize_order<'de, D>(deserializer: D) -> Result<Option<SpaceChildOrder>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match Option::<JsonValue>::deserialize(deserializer)? {
+        Some(JsonValue::String(order)) => order.try_into().ok(),
+        _ => None,
+    })
+}
+
+/// Parses an entry of `via`, returning `None` if it is not a valid server name.
+#[cfg(feature = "compat-lenient-space-child-via")]
+fn parse_via_entry(entry: &JsonValue) -> Option<OwnedServerName> {
+    entry.as_str().and_then(|server_name| server_name.try_into().ok())
+}
+
+/// Deserializes `via`, skipping the entries that are not valid server names.
+#[cfg(feature = "compat-lenient-space-child-via")]
+fn deserialize_via_lenient<'de, D>(deserializer: D) -> Result<Vec<OwnedServerName>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let via = Vec::<JsonValue>::deserialize(deserializer)?;
+    Ok(via.iter().filter_map(parse_via_entry).collect())
+}
+
+/// Deserializes an optional `via`, skipping the entries that are not valid server names.
+#[cfg(feature = "compat-lenient-space-child-via")]
+fn deserialize_possibly_redacted_via_lenient<'de, D>(
+    deserializer: D,
+) -> Result<Option<Vec<OwnedServerName>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let via = Option::<Vec<JsonValue>>::deserialize(deserializer)?;
+    Ok(via.map(|via| via.iter().filter_map(parse_via_entry).collect()))
+}
+
+/// The possibly redacted form of [`SpaceChildEventContent`].
+///
+/// This type is used when it's not obvious whether the content is redacted or not.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct PossiblyRedactedSpaceChildEventContent {
+    /// List of candidate servers that can be used to join the room.
+    ///
+    /// If you activate the `compat-lenient-space-child-via` feature, invalid server names in this
+    /// list are skipped during deserialization instead of making it fail.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(
+        feature = "compat-lenient-space-child-via",
+        serde(deserialize_with = "deserialize_possibly_redacted_via_lenient")
+    )]
+    pub via: Option<Vec<OwnedServerName>>,
+
+    /// Provide a default ordering of siblings in the room list.
+    ///
+    /// Invalid `order`s are deserialized as `None`.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_order",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub order: Option<SpaceChildOrder>,
+
+    /// Space admins can mark particular children of a space as "suggested".
+    ///
+    /// Defaults to `false`.
+    #[serde(default, skip_serializing_if = "ruma_common::serde::is_default")]
+    pub suggested: bool,
+}
+
+impl EventContent for PossiblyRedactedSpaceChildEventContent {
+    type EventType = StateEventType;
+
+    fn event_type(&self) -> Self::EventType {
+        StateEventType::SpaceChild
+    }
+}
+
+impl PossiblyRedactedStateEventContent for PossiblyRedactedSpaceChildEventContent {
+    type StateKey = OwnedRoomId;
+}
+
+impl StaticEventContent for PossiblyRedactedSpaceChildEventContent {
+    const TYPE: &'static str = "m.space.child";
 }
 
 /// An `m.space.child` event represented as a Stripped State Event with an added `origin_server_ts`
@@ -69,19 +335,120 @@ pub struct HierarchySpaceChildEvent {
     pub origin_server_ts: MilliSecondsSinceUnixEpoch,
 }
 
+impl HierarchySpaceChildEvent {
+    /// How far in the future `origin_server_ts` can be to still be considered plausible, to allow
+    /// for clock skew between servers.
+    const MAX_FUTURE_TS_MS: u64 = 24 * 60 * 60 * 1000;
+
+    /// Whether the `origin_server_ts` of this event is plausible, compared to the given current
+    /// time.
+    ///
+    /// Returns `false` if the timestamp is more than one day in the future. Since the ordering of
+    /// space children falls back to `origin_server_ts`, this can be used to filter out or
+    /// deprioritize children with suspicious timestamps.
+    pub fn is_plausible_ts(&self, now: MilliSecondsSinceUnixEpoch) -> bool {
+        u64::from(self.origin_server_ts.get())
+            <= u64::from(now.get()).saturating_add(Self::MAX_FUTURE_TS_MS)
+    }
+
+    /// Converts this event to a stripped state event.
+    ///
+    /// The `origin_server_ts` is dropped, since it is not part of stripped state events. The
+    /// conversion uses [`StateEventContent::into_stripped()`], which never panics with the
+    /// content types from Ruma.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `state_key` is not a valid room ID.
+    pub fn to_stripped(&self) -> Result<Raw<AnyStrippedStateEvent>, IdParseError> {
+        let state_key = OwnedRoomId::try_from(self.state_key.as_str())?;
+        Ok(self.content.clone().into_stripped(self.sender.clone(), state_key))
+    }
+}
+
+/// A wrapper around a space child that implements [`Ord`] according to the [ordering rules] of the
+/// spec.
+///
+/// Children with a valid `order` come first, sorted lexicographically by `order`. They are
+/// followed by children without `order`. Ties are broken by ascending
+/// `origin_server_ts` of the `m.space.child` event, then by ascending room ID.
+///
+/// This can be used to sort children in a `Vec` or to collect them in a `BTreeSet`.
+///
+/// [ordering rules]: https://spec.matrix.org/latest/client-server-api/#ordering-of-children-within-a-space
+#[derive(Clone, Copy, Debug)]
+#[allow(clippy::exhaustive_structs)]
+pub struct SpaceChildSortKey<'a> {
+    /// The content of the `m.space.child` event.
+    pub content: &'a SpaceChildEventContent,
+
+    /// The timestamp of the `m.space.child` event.
+    pub origin_server_ts: MilliSecondsSinceUnixEpoch,
+
+    /// The ID of the child room.
+    pub room_id: &'a RoomId,
+}
+
+impl<'a> SpaceChildSortKey<'a> {
+    /// Creates a new `SpaceChildSortKey` with the given content, timestamp and child room ID.
+    pub fn new(
+        content: &'a SpaceChildEventContent,
+        origin_server_ts: MilliSecondsSinceUnixEpoch,
+        room_id: &'a RoomId,
+    ) -> Self {
+        Self { content, origin_server_ts, room_id }
+    }
+}
+
+impl PartialEq for SpaceChildSortKey<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for SpaceChildSortKey<'_> {}
+
+impl PartialOrd for SpaceChildSortKey<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SpaceChildSortKey<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let order = match (&self.content.order, &other.content.order) {
+            (Some(order), Some(other_order)) => order.cmp(other_order),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+
+        order
+            .then_with(|| self.origin_server_ts.cmp(&other.origin_server_ts))
+            .then_with(|| self.room_id.cmp(other.room_id))
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use assert_matches2::assert_matches;
     use js_int::uint;
-    use ruma_common::{server_name, MilliSecondsSinceUnixEpoch};
+    use ruma_common::{
+        owned_room_id, owned_user_id, room_id, server_name, MilliSecondsSinceUnixEpoch,
+    };
     use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
-    use super::{HierarchySpaceChildEvent, SpaceChildEventContent};
+    use super::{
+        HierarchySpaceChildEvent, SpaceChildError, SpaceChildEventContent, SpaceChildOrder,
+        SpaceChildOrderError, SpaceChildSortKey,
+    };
+    use crate::AnyStrippedStateEvent;
 
     #[test]
     fn space_child_serialization() {
         let content = SpaceChildEventContent {
             via: vec![server_name!("example.com").to_owned()],
-            order: Some("uwu".to_owned()),
+            order: Some("uwu".try_into().unwrap()),
             suggested: false,
         };
 
@@ -124,4 +491,245 @@ mod tests {
         assert_eq!(ev.content.order, None);
         assert!(!ev.content.suggested);
     }
+
+    #[test]
+    fn into_child_event() {
+        let ev = SpaceChildEventContent::new(vec![server_name!("example.org").to_owned()])
+            .into_child_event(
+                owned_room_id!("!a:example.org"),
+                owned_user_id!("@alice:example.org"),
+                MilliSecondsSinceUnixEpoch(uint!(1_629_413_349)),
+            );
+
+        assert_eq!(ev.origin_server_ts, MilliSecondsSinceUnixEpoch(uint!(1_629_413_349)));
+        assert_eq!(ev.sender, "@alice:example.org");
+        assert_eq!(ev.state_key, "!a:example.org");
+        assert_eq!(ev.content.via, ["example.org"]);
+    }
+
+    #[test]
+    fn hierarchy_space_child_to_stripped() {
+        let ev = SpaceChildEventContent::new(vec![server_name!("example.org").to_owned()])
+            .into_child_event(
+                owned_room_id!("!a:example.org"),
+                owned_user_id!("@alice:example.org"),
+                MilliSecondsSinceUnixEpoch(uint!(1_629_413_349)),
+            );
+        let stripped = ev.to_stripped().unwrap();
+
+        assert_eq!(
+            to_json_value(&stripped).unwrap(),
+            json!({
+                "content": {
+                    "via": ["example.org"],
+                },
+                "sender": "@alice:example.org",
+                "state_key": "!a:example.org",
+                "type": "m.space.child",
+            })
+        );
+        assert_matches!(stripped.deserialize(), Ok(AnyStrippedStateEvent::SpaceChild(stripped)));
+        assert_eq!(stripped.sender, "@alice:example.org");
+        assert_eq!(stripped.state_key, "!a:example.org");
+        assert_eq!(stripped.content.via.unwrap(), ["example.org"]);
+    }
+
+    #[test]
+    fn hierarchy_space_child_invalid_state_key_to_stripped() {
+        let mut ev = SpaceChildEventContent::new(vec![server_name!("example.org").to_owned()])
+            .into_child_event(
+                owned_room_id!("!a:example.org"),
+                owned_user_id!("@alice:example.org"),
+                MilliSecondsSinceUnixEpoch(uint!(1_629_413_349)),
+            );
+        ev.state_key = "not a room ID".to_owned();
+
+        ev.to_stripped().unwrap_err();
+    }
+
+    #[test]
+    fn space_child_invalid_via_deserialization() {
+        let json = json!({
+            "via": ["example.org", "not a server name", 42],
+            "order": "uwu",
+        });
+
+        #[cfg(not(feature = "compat-lenient-space-child-via"))]
+        from_json_value::<SpaceChildEventContent>(json).unwrap_err();
+        #[cfg(feature = "compat-lenient-space-child-via")]
+        {
+            let content = from_json_value::<SpaceChildEventContent>(json.clone()).unwrap();
+            assert_eq!(content.via, ["example.org"]);
+            assert_eq!(content.order.as_ref().map(SpaceChildOrder::as_str), Some("uwu"));
+            assert!(!content.suggested);
+
+            let raw = ruma_common::serde::Raw::new(&json).unwrap().cast::<SpaceChildEventContent>();
+            assert_eq!(SpaceChildEventContent::invalid_via_count(&raw), 2);
+        }
+    }
+
+    #[test]
+    fn stripped_space_child_invalid_via_deserialization() {
+        let json = json!({
+            "content": {
+                "via": ["example.org", "not a server name", 42],
+            },
+            "sender": "@alice:example.org",
+            "state_key": "!a:example.org",
+            "type": "m.space.child",
+        });
+
+        #[cfg(not(feature = "compat-lenient-space-child-via"))]
+        from_json_value::<AnyStrippedStateEvent>(json).unwrap_err();
+        #[cfg(feature = "compat-lenient-space-child-via")]
+        {
+            assert_matches!(
+                from_json_value::<AnyStrippedStateEvent>(json),
+                Ok(AnyStrippedStateEvent::SpaceChild(stripped))
+            );
+            assert_eq!(stripped.content.via.unwrap(), ["example.org"]);
+        }
+    }
+
+    #[test]
+    fn hierarchy_space_child_plausible_ts() {
+        let now = MilliSecondsSinceUnixEpoch(uint!(1_000_000_000));
+        let mut event = HierarchySpaceChildEvent {
+            content: SpaceChildEventContent::new(vec![]),
+            sender: owned_user_id!("@example:localhost"),
+            state_key: "!room:localhost".to_owned(),
+            origin_server_ts: MilliSecondsSinceUnixEpoch(uint!(900_000_000)),
+        };
+        assert!(event.is_plausible_ts(now));
+
+        event.origin_server_ts = MilliSecondsSinceUnixEpoch(uint!(1_000_060_000));
+        assert!(event.is_plausible_ts(now));
+
+        event.origin_server_ts = MilliSecondsSinceUnixEpoch(uint!(2_000_000_000));
+        assert!(!event.is_plausible_ts(now));
+    }
+
+    #[test]
+    fn space_child_order() {
+        let mut ordered = SpaceChildEventContent::new(vec![]);
+        ordered.order = Some("a".try_into().unwrap());
+        let mut ordered_last = SpaceChildEventContent::new(vec![]);
+        ordered_last.order = Some("b".try_into().unwrap());
+        let invalid_order: SpaceChildEventContent =
+            from_json_value(json!({ "via": [], "order": "\u{1F600}" })).unwrap();
+        let unordered = SpaceChildEventContent::new(vec![]);
+
+        let mut children = [
+            SpaceChildSortKey::new(
+                &unordered,
+                MilliSecondsSinceUnixEpoch(uint!(2)),
+                room_id!("!a:localhost"),
+            ),
+            SpaceChildSortKey::new(
+                &invalid_order,
+                MilliSecondsSinceUnixEpoch(uint!(1)),
+                room_id!("!b:localhost"),
+            ),
+            SpaceChildSortKey::new(
+                &ordered_last,
+                MilliSecondsSinceUnixEpoch(uint!(1)),
+                room_id!("!c:localhost"),
+            ),
+            SpaceChildSortKey::new(
+                &unordered,
+                MilliSecondsSinceUnixEpoch(uint!(1)),
+                room_id!("!d:localhost"),
+            ),
+            SpaceChildSortKey::new(
+                &ordered,
+                MilliSecondsSinceUnixEpoch(uint!(3)),
+                room_id!("!e:localhost"),
+            ),
+        ];
+        children.sort();
+
+        let room_ids = children.iter().map(|child| child.room_id.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            room_ids,
+            ["!e:localhost", "!c:localhost", "!b:localhost", "!d:localhost", "!a:localhost"]
+        );
+    }
+
+    #[test]
+    fn space_child_order_validation() {
+        assert_eq!(SpaceChildOrder::try_from("a").unwrap().as_str(), "a");
+        assert_eq!(SpaceChildOrder::try_from(" ~").unwrap().as_str(), " ~");
+        assert_eq!(
+            SpaceChildOrder::try_from("a".repeat(SpaceChildOrder::MAX_LEN)).unwrap().as_str().len(),
+            SpaceChildOrder::MAX_LEN
+        );
+        assert_eq!(
+            SpaceChildOrder::try_from("a".repeat(SpaceChildOrder::MAX_LEN + 1)),
+            Err(SpaceChildOrderError::TooLong)
+        );
+        assert_eq!(SpaceChildOrder::try_from("a\n"), Err(SpaceChildOrderError::InvalidCharacter));
+        assert_eq!(SpaceChildOrder::try_from("é"), Err(SpaceChildOrderError::InvalidCharacter));
+
+        let content: SpaceChildEventContent =
+            from_json_value(json!({ "via": [], "order": "\u{1F600}" })).unwrap();
+        assert_eq!(content.order, None);
+        let content: SpaceChildEventContent =
+            from_json_value(json!({ "via": [], "order": 42 })).unwrap();
+        assert_eq!(content.order, None);
+        let content: SpaceChildEventContent =
+            from_json_value(json!({ "via": [], "order": "a".repeat(51) })).unwrap();
+        assert_eq!(content.order, None);
+        let content: SpaceChildEventContent =
+            from_json_value(json!({ "via": [], "order": "abc" })).unwrap();
+        assert_eq!(content.order.unwrap().as_str(), "abc");
+    }
+
+    #[test]
+    fn space_child_best_via() {
+        let content = SpaceChildEventContent::new(vec![
+            server_name!("a.localhost").to_owned(),
+            server_name!("b.localhost").to_owned(),
+            server_name!("a.localhost").to_owned(),
+            server_name!("child.localhost").to_owned(),
+        ]);
+        let child = room_id!("!room:child.localhost");
+
+        assert_eq!(
+            content.best_via(child, 2),
+            [server_name!("child.localhost"), server_name!("a.localhost")]
+        );
+        assert_eq!(
+            content.best_via(child, 10),
+            [
+                server_name!("child.localhost"),
+                server_name!("a.localhost"),
+                server_name!("b.localhost")
+            ]
+        );
+        assert_eq!(
+            content.best_via(room_id!("!room:other.localhost"), 10),
+            [
+                server_name!("a.localhost"),
+                server_name!("b.localhost"),
+                server_name!("child.localhost")
+            ]
+        );
+        assert!(content.best_via(child, 0).is_empty());
+    }
+
+    #[test]
+    fn space_child_validate() {
+        let mut content = SpaceChildEventContent::new(vec![]);
+        content.validate().unwrap();
+
+        content.suggested = true;
+        assert_eq!(content.validate(), Err(SpaceChildError::SuggestedWithoutVia));
+
+        let mut content: SpaceChildEventContent =
+            from_json_value(json!({ "via": [], "suggested": true })).unwrap();
+        assert_eq!(content.validate(), Err(SpaceChildError::SuggestedWithoutVia));
+
+        content.via.push(server_name!("example.com").to_owned());
+        content.validate().unwrap();
+    }
 }
@@ -0,0 +1,54 @@
+//! Helpers to test event types.
+//!
+//! This module is only available with the `testing` cargo feature.
+
+use serde::de::DeserializeOwned;
+
+use crate::room::{create::RoomCreateEventContent, message::RoomMessageEventContent};
+
+/// Try to deserialize the given bytes as JSON into each supported event content type.
+///
+/// Deserialization errors are ignored, so this function only panics if the deserialization code
+/// panics. This makes it suitable as a fuzzing target.
+///
+/// The supported types are:
+///
+/// * [`RoomCreateEventContent`]
+/// * [`RoomMessageEventContent`]
+pub fn fuzz_deserialize(bytes: &[u8]) {
+    try_deserialize::<RoomCreateEventContent>(bytes);
+    try_deserialize::<RoomMessageEventContent>(bytes);
+}
+
+fn try_deserialize<T: DeserializeOwned>(bytes: &[u8]) {
+    // Errors are expected with arbitrary input.
+    let _ = serde_json::from_slice::<T>(bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fuzz_deserialize;
+
+    #[test]
+    fn fuzz_deserialize_malformed_input() {
+        let inputs: &[&[u8]] = &[
+            b"",
+            b"\xff\xfe",
+            b"null",
+            b"[]",
+            b"{}",
+            br#"{"msgtype": "m.text"}"#,
+            br#"{"msgtype": 1, "body": []}"#,
+            br#"{"msgtype": "m.image", "body": "", "url": "invalid", "file": null}"#,
+            br#"{"msgtype": "m.text", "body": "", "m.relates_to": {"rel_type": "m.thread"}}"#,
+            br#"{"m.federate": "maybe", "room_version": {}, "predecessor": 1}"#,
+            br#"{"room_version": "", "type": null}"#,
+        ];
+
+        for input in inputs {
+            fuzz_deserialize(input);
+        }
+
+        fuzz_deserialize(&[b'['; 10_000]);
+    }
+}
@@ -1,18 +1,27 @@
 use js_int::Int;
 use ruma_common::{
-    serde::CanBeEmpty, MilliSecondsSinceUnixEpoch, OwnedEventId, OwnedTransactionId, OwnedUserId,
+    serde::CanBeEmpty, EventId, MilliSecondsSinceUnixEpoch, OwnedEventId, OwnedTransactionId,
+    OwnedUserId, TransactionId, UserId,
 };
-use serde::{de::DeserializeOwned, Deserialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use super::{
-    relation::{BundledMessageLikeRelations, BundledStateRelations},
+    relation::{
+        BundledMessageLikeRelations, BundledReference, BundledStateRelations, BundledThread,
+    },
     room::redaction::RoomRedactionEventContent,
     MessageLikeEventContent, OriginalSyncMessageLikeEvent, PossiblyRedactedStateEventContent,
 };
 
 /// Extra information about a message event that is not incorporated into the event's hash.
-#[derive(Clone, Debug, Deserialize)]
-#[serde(bound = "OriginalSyncMessageLikeEvent<C>: DeserializeOwned")]
+///
+/// Like [`BundledMessageLikeRelations`], this type only implements `Serialize` if the type of the
+/// bundled replacement event does.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(bound(
+    deserialize = "OriginalSyncMessageLikeEvent<C>: DeserializeOwned",
+    serialize = "OriginalSyncMessageLikeEvent<C>: Serialize"
+))]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
 pub struct MessageLikeUnsigned<C: MessageLikeEventContent> {
     /// The time in milliseconds that has elapsed since the event was sent.
@@ -20,16 +29,22 @@ pub struct MessageLikeUnsigned<C: MessageLikeEventContent> {
     /// This field is generated by the local homeserver, and may be incorrect if the local time on
     /// at least one of the two servers is out of sync, which can cause the age to either be
     /// negative or greater than it actually is.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub age: Option<Int>,
 
     /// The client-supplied transaction ID, if the client being given the event is the same one
     /// which sent it.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub transaction_id: Option<OwnedTransactionId>,
 
     /// [Bundled aggregations] of related child events.
     ///
     /// [Bundled aggregations]: https://spec.matrix.org/latest/client-server-api/#aggregations-of-child-events
-    #[serde(rename = "m.relations", default)]
+    #[serde(
+        rename = "m.relations",
+        default,
+        skip_serializing_if = "BundledMessageLikeRelations::is_empty"
+    )]
     pub relations: BundledMessageLikeRelations<OriginalSyncMessageLikeEvent<C>>,
 }
 
@@ -38,6 +53,43 @@ impl<C: MessageLikeEventContent> MessageLikeUnsigned<C> {
     pub fn new() -> Self {
         Self { age: None, transaction_id: None, relations: BundledMessageLikeRelations::default() }
     }
+
+    /// Create a new `Unsigned` with the given bundled aggregations and other fields set to `None`.
+    pub fn with_relations(
+        relations: BundledMessageLikeRelations<OriginalSyncMessageLikeEvent<C>>,
+    ) -> Self {
+        Self { relations, ..Self::new() }
+    }
+
+    /// Replace the bundled aggregations of this `Unsigned`.
+    pub fn set_relations(
+        &mut self,
+        relations: BundledMessageLikeRelations<OriginalSyncMessageLikeEvent<C>>,
+    ) {
+        self.relations = relations;
+    }
+
+    /// Merge the unsigned data computed by the server (`self`) with the unsigned data associated
+    /// with the client that sent the event (`other`).
+    ///
+    /// The `age` and `relations` of `self` take precedence, and those of `other` are only used if
+    /// they are not set in `self`. The `transaction_id` of `other` takes precedence, since it
+    /// identifies the client that sent the event.
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            age: self.age.or(other.age),
+            transaction_id: other.transaction_id.or(self.transaction_id),
+            relations: if self.relations.is_empty() { other.relations } else { self.relations },
+        }
+    }
+
+    /// Whether the client-supplied transaction ID of this event matches the given one.
+    ///
+    /// This can be used to match an event received from the homeserver with its local echo.
+    /// Returns `false` if there is no transaction ID.
+    pub fn matches_transaction(&self, txn_id: &TransactionId) -> bool {
+        self.transaction_id.as_deref() == Some(txn_id)
+    }
 }
 
 impl<C: MessageLikeEventContent> Default for MessageLikeUnsigned<C> {
@@ -58,7 +110,7 @@ impl<C: MessageLikeEventContent> CanBeEmpty for MessageLikeUnsigned<C> {
 }
 
 /// Extra information about a state event that is not incorporated into the event's hash.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
 pub struct StateUnsigned<C: PossiblyRedactedStateEventContent> {
     /// The time in milliseconds that has elapsed since the event was sent.
@@ -66,19 +118,26 @@ pub struct StateUnsigned<C: PossiblyRedactedStateEventContent> {
     /// This field is generated by the local homeserver, and may be incorrect if the local time on
     /// at least one of the two servers is out of sync, which can cause the age to either be
     /// negative or greater than it actually is.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub age: Option<Int>,
 
     /// The client-supplied transaction ID, if the client being given the event is the same one
     /// which sent it.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub transaction_id: Option<OwnedTransactionId>,
 
     /// Optional previous content of the event.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub prev_content: Option<C>,
 
     /// [Bundled aggregations] of related child events.
     ///
     /// [Bundled aggregations]: https://spec.matrix.org/latest/client-server-api/#aggregations-of-child-events
-    #[serde(rename = "m.relations", default)]
+    #[serde(
+        rename = "m.relations",
+        default,
+        skip_serializing_if = "BundledStateRelations::is_empty"
+    )]
     pub relations: BundledStateRelations,
 }
 
@@ -87,6 +146,25 @@ impl<C: PossiblyRedactedStateEventContent> StateUnsigned<C> {
     pub fn new() -> Self {
         Self { age: None, transaction_id: None, prev_content: None, relations: Default::default() }
     }
+
+    /// Whether the client-supplied transaction ID of this event matches the given one.
+    ///
+    /// This can be used to match an event received from the homeserver with its local echo.
+    /// Returns `false` if there is no transaction ID.
+    pub fn matches_transaction(&self, txn_id: &TransactionId) -> bool {
+        self.transaction_id.as_deref() == Some(txn_id)
+    }
+
+    /// Whether the given current content of the event differs from its `prev_content`.
+    ///
+    /// Returns `true` if there is no `prev_content`, since the state was then set for the first
+    /// time, or its previous content is unknown.
+    pub fn changed(&self, current: &C) -> bool
+    where
+        C: PartialEq,
+    {
+        self.prev_content.as_ref() != Some(current)
+    }
 }
 
 impl<C: PossiblyRedactedStateEventContent> CanBeEmpty for StateUnsigned<C> {
@@ -115,12 +193,42 @@ impl<C: PossiblyRedactedStateEventContent> Default for StateUnsigned<C> {
 pub struct RedactedUnsigned {
     /// The event that redacted this event, if any.
     pub redacted_because: UnsignedRoomRedactionEvent,
+
+    /// [Bundled aggregations] of related child events.
+    ///
+    /// Replacements are not included, since servers don't apply edits to redacted events.
+    ///
+    /// [Bundled aggregations]: https://spec.matrix.org/latest/client-server-api/#aggregations-of-child-events
+    #[serde(rename = "m.relations", default)]
+    pub relations: BundledStateRelations,
 }
 
 impl RedactedUnsigned {
     /// Create a new `RedactedUnsigned` with the given redaction event.
     pub fn new(redacted_because: UnsignedRoomRedactionEvent) -> Self {
-        Self { redacted_because }
+        Self { redacted_because, relations: BundledStateRelations::new() }
+    }
+
+    /// The bundled thread, if this event is the root of a thread.
+    pub fn thread(&self) -> Option<&BundledThread> {
+        self.relations.thread()
+    }
+
+    /// The bundled references to this event.
+    ///
+    /// Returns an empty slice if there are none.
+    pub fn references(&self) -> &[BundledReference] {
+        self.relations.references()
+    }
+
+    /// The ID of the event that redacted this event.
+    pub fn redaction_event_id(&self) -> &EventId {
+        &self.redacted_because.event_id
+    }
+
+    /// The ID of the user who redacted this event.
+    pub fn redactor(&self) -> &UserId {
+        &self.redacted_because.sender
     }
 }
 
@@ -131,7 +239,8 @@ impl RedactedUnsigned {
 /// field here.
 ///
 /// It is intentionally not possible to create an instance of this type other than through `Clone`
-/// or `Deserialize`.
+/// or `Deserialize`, except with [`UnsignedRoomRedactionEvent::new()`] when the `testing` cargo
+/// feature is activated.
 #[derive(Clone, Debug, Deserialize)]
 #[non_exhaustive]
 pub struct UnsignedRoomRedactionEvent {
@@ -151,3 +260,197 @@ pub struct UnsignedRoomRedactionEvent {
     #[serde(default)]
     pub unsigned: MessageLikeUnsigned<RoomRedactionEventContent>,
 }
+
+impl UnsignedRoomRedactionEvent {
+    /// Creates a new `UnsignedRoomRedactionEvent` with the given content, event ID, sender and
+    /// timestamp.
+    ///
+    /// This is only meant to be used in tests, for example to construct a [`RedactedUnsigned`].
+    #[cfg(feature = "testing")]
+    pub fn new(
+        content: RoomRedactionEventContent,
+        event_id: OwnedEventId,
+        sender: OwnedUserId,
+        origin_server_ts: MilliSecondsSinceUnixEpoch,
+    ) -> Self {
+        Self { content, event_id, sender, origin_server_ts, unsigned: MessageLikeUnsigned::new() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use js_int::int;
+    use ruma_common::{owned_event_id, OwnedTransactionId, TransactionId};
+    use ruma_macros::EventContent;
+    use serde::{Deserialize, Serialize};
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::{MessageLikeUnsigned, RedactedUnsigned, StateUnsigned};
+    use crate::{
+        relation::{BundledMessageLikeRelations, BundledReference, ReferenceChunk},
+        room::{
+            member::{MembershipState, RoomMemberEventContent},
+            message::RoomMessageEventContent,
+        },
+    };
+
+    #[test]
+    fn state_unsigned_serialization() {
+        let unsigned = StateUnsigned::<RoomMemberEventContent>::new();
+        assert_eq!(to_json_value(&unsigned).unwrap(), json!({}));
+
+        let mut unsigned = StateUnsigned::<RoomMemberEventContent>::new();
+        unsigned.age = Some(int!(100));
+        unsigned.transaction_id = Some(OwnedTransactionId::from("txn1"));
+        unsigned.prev_content = Some(RoomMemberEventContent::new(MembershipState::Invite));
+        unsigned.relations.reference =
+            Some(Box::new(ReferenceChunk::new(vec![BundledReference::new(owned_event_id!(
+                "$reference"
+            ))])));
+
+        assert_eq!(
+            to_json_value(&unsigned).unwrap(),
+            json!({
+                "age": 100,
+                "transaction_id": "txn1",
+                "prev_content": { "membership": "invite" },
+                "m.relations": {
+                    "m.reference": {
+                        "chunk": [{ "event_id": "$reference" }],
+                    },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn merge() {
+        let mut server = MessageLikeUnsigned::<RoomMessageEventContent>::new();
+        server.age = Some(int!(100));
+        server.transaction_id = Some(OwnedTransactionId::from("server_txn"));
+
+        let mut client = MessageLikeUnsigned::<RoomMessageEventContent>::new();
+        client.age = Some(int!(5));
+        client.transaction_id = Some(OwnedTransactionId::from("client_txn"));
+        client.relations.reference =
+            Some(Box::new(ReferenceChunk::new(vec![BundledReference::new(owned_event_id!(
+                "$reference"
+            ))])));
+
+        let merged = server.merge(client);
+        assert_eq!(merged.age, Some(int!(100)));
+        assert_eq!(merged.transaction_id.as_deref(), Some(<&TransactionId>::from("client_txn")));
+        assert!(merged.relations.reference.is_some());
+    }
+
+    #[test]
+    fn with_relations() {
+        let mut relations = BundledMessageLikeRelations::new();
+        relations.reference = Some(Box::new(ReferenceChunk::new(vec![BundledReference::new(
+            owned_event_id!("$reference"),
+        )])));
+
+        let mut unsigned =
+            MessageLikeUnsigned::<RoomMessageEventContent>::with_relations(relations);
+        assert_eq!(unsigned.age, None);
+        assert_eq!(unsigned.transaction_id, None);
+        assert_eq!(unsigned.relations.reference.as_ref().unwrap().chunk[0].event_id, "$reference");
+
+        unsigned.set_relations(BundledMessageLikeRelations::new());
+        assert!(unsigned.relations.is_empty());
+    }
+
+    #[test]
+    fn state_unsigned_changed() {
+        #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, EventContent)]
+        #[ruma_event(type = "org.example.test", kind = State, state_key_type = String)]
+        struct TestEventContent {
+            name: Option<String>,
+        }
+
+        let current = TestEventContent { name: Some("new".to_owned()) };
+
+        let mut unsigned = StateUnsigned::<TestEventContent>::new();
+        assert!(unsigned.changed(&current));
+
+        unsigned.prev_content = Some(TestEventContent { name: Some("old".to_owned()) });
+        assert!(unsigned.changed(&current));
+
+        unsigned.prev_content = Some(current.clone());
+        assert!(!unsigned.changed(&current));
+    }
+
+    #[test]
+    fn matches_transaction() {
+        let txn_id = OwnedTransactionId::from("txn1");
+
+        let mut unsigned = MessageLikeUnsigned::<RoomMessageEventContent>::new();
+        assert!(!unsigned.matches_transaction(&txn_id));
+        unsigned.transaction_id = Some(txn_id.clone());
+        assert!(unsigned.matches_transaction(&txn_id));
+        assert!(!unsigned.matches_transaction(<&TransactionId>::from("txn2")));
+
+        let mut unsigned = StateUnsigned::<RoomMemberEventContent>::new();
+        assert!(!unsigned.matches_transaction(&txn_id));
+        unsigned.transaction_id = Some(txn_id.clone());
+        assert!(unsigned.matches_transaction(&txn_id));
+        assert!(!unsigned.matches_transaction(<&TransactionId>::from("txn2")));
+    }
+
+    #[test]
+    #[cfg(feature = "testing")]
+    fn redacted_unsigned_with_new_redaction_event() {
+        use js_int::uint;
+        use ruma_common::{owned_user_id, MilliSecondsSinceUnixEpoch};
+
+        use super::UnsignedRoomRedactionEvent;
+        use crate::room::redaction::RoomRedactionEventContent;
+
+        let unsigned = RedactedUnsigned::new(UnsignedRoomRedactionEvent::new(
+            RoomRedactionEventContent::new_v1().with_reason("Spam".to_owned()),
+            owned_event_id!("$redaction"),
+            owned_user_id!("@moderator:example.org"),
+            MilliSecondsSinceUnixEpoch(uint!(10_000)),
+        ));
+
+        assert_eq!(unsigned.redaction_event_id(), "$redaction");
+        assert_eq!(unsigned.redactor(), "@moderator:example.org");
+        assert_eq!(unsigned.redacted_because.content.reason.as_deref(), Some("Spam"));
+    }
+
+    #[test]
+    fn redacted_unsigned_with_relations() {
+        let unsigned: RedactedUnsigned = from_json_value(json!({
+            "redacted_because": {
+                "content": {},
+                "event_id": "$redaction",
+                "origin_server_ts": 10_000,
+                "sender": "@moderator:example.org",
+                "type": "m.room.redaction",
+            },
+            "m.relations": {
+                "m.reference": {
+                    "chunk": [{ "event_id": "$reference" }],
+                },
+            },
+        }))
+        .unwrap();
+
+        assert_eq!(unsigned.redaction_event_id(), "$redaction");
+        assert!(unsigned.thread().is_none());
+        assert_eq!(unsigned.references().len(), 1);
+        assert_eq!(unsigned.references()[0].event_id, "$reference");
+
+        let unsigned: RedactedUnsigned = from_json_value(json!({
+            "redacted_because": {
+                "content": {},
+                "event_id": "$redaction",
+                "origin_server_ts": 10_000,
+                "sender": "@moderator:example.org",
+                "type": "m.room.redaction",
+            },
+        }))
+        .unwrap();
+        assert!(unsigned.relations.is_empty());
+    }
+}
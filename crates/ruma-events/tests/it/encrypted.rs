@@ -501,3 +501,22 @@ fn custom_relation_serialization() {
         })
     );
 }
+
+#[test]
+fn annotation_relation() {
+    let relation = Relation::annotation(owned_event_id!("$annotated"), "👍".to_owned());
+    assert_eq!(relation.as_annotation(), Some((&*owned_event_id!("$annotated"), "👍")));
+
+    let content = RoomEncryptedEventContent::new(encrypted_scheme(), Some(relation));
+    assert_eq!(
+        to_json_value(&content).unwrap()["m.relates_to"],
+        json!({
+            "rel_type": "m.annotation",
+            "event_id": "$annotated",
+            "key": "👍",
+        })
+    );
+
+    let reference = Relation::Reference(Reference::new(owned_event_id!("$referenced")));
+    assert_eq!(reference.as_annotation(), None);
+}
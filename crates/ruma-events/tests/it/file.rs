@@ -174,6 +174,28 @@ fn encrypted_content_deserialization() {
     assert_eq!(content.file.url, "mxc://notareal.hs/abcdef");
     assert_eq!(content.file.name, "");
     assert!(content.file.encryption_info.is_some());
+    assert!(content.file.extra.is_empty());
+}
+
+#[test]
+fn extra_file_fields_roundtrip() {
+    let json_data = json!({
+        "org.matrix.msc1767.text": [
+            { "body": "Upload: my_file.bin" },
+        ],
+        "org.matrix.msc1767.file": {
+            "url": "mxc://notareal.hs/abcdef",
+            "name": "my_file.bin",
+            "mimetype": "application/vnd.example.custom",
+            "com.example.checksum": "abc123",
+        }
+    });
+
+    let content = from_json_value::<FileEventContent>(json_data.clone()).unwrap();
+    assert_eq!(content.file.mimetype.as_deref(), Some("application/vnd.example.custom"));
+    assert_eq!(content.file.extra.len(), 1);
+    assert_eq!(content.file.extra["com.example.checksum"], "abc123");
+    assert_eq!(to_json_value(&content).unwrap(), json_data);
 }
 
 #[test]
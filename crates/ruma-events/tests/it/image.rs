@@ -196,6 +196,7 @@ fn plain_content_deserialization() {
     assert_eq!(content.file.url, "mxc://notareal.hs/abcdef");
     assert_eq!(content.file.name, "my_cat.png");
     assert_matches!(content.file.encryption_info, None);
+    assert_eq!(content.caption_text(), Some("Look at my cat!"));
     let image_details = content.image_details.unwrap();
     assert_eq!(image_details.width, uint!(668));
     assert_eq!(image_details.height, uint!(1023));
@@ -255,6 +256,7 @@ fn encrypted_content_deserialization() {
     assert_eq!(thumbnail.image_details.width, uint!(480));
     assert_eq!(thumbnail.image_details.height, uint!(560));
     assert!(content.caption.is_none());
+    assert_eq!(content.caption_text(), None);
 }
 
 #[test]
@@ -205,6 +205,9 @@ fn html_and_text_content_deserialization() {
     let content = from_json_value::<MessageEventContent>(json_data).unwrap();
     assert_eq!(content.text.find_plain(), Some("Hello, New World!"));
     assert_eq!(content.text.find_html(), Some("Hello, <em>New World</em>!"));
+    assert_eq!(content.text.mimetypes(), ["text/html", "text/plain"]);
+    assert_eq!(content.text.representation("text/html"), Some("Hello, <em>New World</em>!"));
+    assert_eq!(content.text.representation("text/markdown"), None);
     #[cfg(feature = "unstable-msc3955")]
     assert!(!content.automated);
 }
@@ -66,6 +66,8 @@ fn deserialize_redacted_aliases() {
     );
     assert_eq!(redacted.event_id, "$h29iv0s8:example.com");
     assert_eq!(redacted.content.aliases, None);
+    assert_eq!(redacted.unsigned.redaction_event_id(), "$h29iv0s8:example.com");
+    assert_eq!(redacted.unsigned.redactor(), "@carl:example.com");
 }
 
 #[test]
@@ -132,7 +134,7 @@ fn deserialize_redacted_state_event() {
         ),)))
     );
     assert_eq!(redacted.event_id, "$h29iv0s8:example.com");
-    assert_eq!(redacted.content.creator.unwrap(), "@carl:example.com");
+    assert_eq!(redacted.content.creator.as_deref().unwrap(), "@carl:example.com");
 }
 
 #[test]
@@ -223,9 +225,6 @@ fn redact_state_content() {
     let raw_json = to_raw_json_value(&json).unwrap();
     let content = RoomCreateEventContent::from_parts("m.room.create", &raw_json).unwrap();
 
-    assert_matches!(
-        content.redact(&RoomVersionId::V6),
-        RedactedRoomCreateEventContent { creator, .. }
-    );
-    assert_eq!(creator.unwrap(), "@carl:example.com");
+    let redacted: RedactedRoomCreateEventContent = content.redact(&RoomVersionId::V6);
+    assert_eq!(redacted.creator.as_deref().unwrap(), "@carl:example.com");
 }
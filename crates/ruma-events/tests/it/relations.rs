@@ -1,9 +1,10 @@
 use assert_matches2::assert_matches;
 use assign::assign;
+use js_int::uint;
 use ruma_common::owned_event_id;
 use ruma_events::{
     relation::{CustomRelation, InReplyTo, Replacement, Thread},
-    room::message::{MessageType, Relation, RoomMessageEventContent},
+    room::message::{MessageType, OriginalSyncRoomMessageEvent, Relation, RoomMessageEventContent},
 };
 use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
@@ -170,6 +171,84 @@ fn thread_reply_serialize() {
     );
 }
 
+#[test]
+fn thread_constructors_roundtrip() {
+    let thread_root = owned_event_id!("$1598361704261elfgc");
+
+    let content = assign!(RoomMessageEventContent::text_plain("<text msg>"), {
+        relates_to: Some(Relation::thread(thread_root.clone())),
+    });
+    let json = json!({
+        "msgtype": "m.text",
+        "body": "<text msg>",
+        "m.relates_to": {
+            "rel_type": "m.thread",
+            "event_id": "$1598361704261elfgc",
+        },
+    });
+    assert_eq!(to_json_value(&content).unwrap(), json);
+    assert_matches!(
+        from_json_value::<RoomMessageEventContent>(json).unwrap().relates_to,
+        Some(Relation::Thread(thread))
+    );
+    assert_eq!(thread.event_id, thread_root);
+    assert_matches!(thread.in_reply_to, None);
+    assert!(!thread.is_falling_back);
+
+    let content = assign!(RoomMessageEventContent::text_plain("<text msg>"), {
+        relates_to: Some(Relation::thread_plain(
+            thread_root.clone(),
+            owned_event_id!("$latesteventid"),
+        )),
+    });
+    let json = json!({
+        "msgtype": "m.text",
+        "body": "<text msg>",
+        "m.relates_to": {
+            "rel_type": "m.thread",
+            "event_id": "$1598361704261elfgc",
+            "m.in_reply_to": {
+                "event_id": "$latesteventid",
+            },
+            "is_falling_back": true,
+        },
+    });
+    assert_eq!(to_json_value(&content).unwrap(), json);
+    assert_matches!(
+        from_json_value::<RoomMessageEventContent>(json).unwrap().relates_to,
+        Some(Relation::Thread(thread))
+    );
+    assert_eq!(thread.event_id, thread_root);
+    assert_eq!(thread.in_reply_to.unwrap().event_id, "$latesteventid");
+    assert!(thread.is_falling_back);
+
+    let content = assign!(RoomMessageEventContent::text_plain("<text msg>"), {
+        relates_to: Some(Relation::thread_reply(
+            thread_root.clone(),
+            owned_event_id!("$repliedtoeventid"),
+        )),
+    });
+    let json = json!({
+        "msgtype": "m.text",
+        "body": "<text msg>",
+        "m.relates_to": {
+            "rel_type": "m.thread",
+            "event_id": "$1598361704261elfgc",
+            "m.in_reply_to": {
+                "event_id": "$repliedtoeventid",
+            },
+        },
+    });
+    assert_eq!(to_json_value(&content).unwrap(), json);
+    assert_matches!(
+        from_json_value::<RoomMessageEventContent>(json).unwrap().relates_to,
+        Some(Relation::Thread(thread))
+    );
+    assert_eq!(thread.event_id, thread_root);
+    assert_eq!(thread.in_reply_to.unwrap().event_id, "$repliedtoeventid");
+    assert!(!thread.is_falling_back);
+}
+
 #[test]
 fn thread_stable_deserialize() {
     let json = json!({
@@ -299,3 +378,53 @@ fn custom_serialize() {
         })
     );
 }
+
+#[test]
+fn bundled_relations_accessors() {
+    let json = json!({
+        "content": {
+            "msgtype": "m.text",
+            "body": "Thread root",
+        },
+        "event_id": "$root",
+        "origin_server_ts": 1,
+        "sender": "@alice:example.org",
+        "type": "m.room.message",
+        "unsigned": {
+            "m.relations": {
+                "m.thread": {
+                    "latest_event": {
+                        "content": {
+                            "msgtype": "m.text",
+                            "body": "Latest",
+                        },
+                        "event_id": "$latest",
+                        "origin_server_ts": 2,
+                        "room_id": "!room:example.org",
+                        "sender": "@bob:example.org",
+                        "type": "m.room.message",
+                    },
+                    "count": 7,
+                    "current_user_participated": false,
+                },
+                "m.reference": {
+                    "chunk": [
+                        { "event_id": "$ref1" },
+                        { "event_id": "$ref2" },
+                    ],
+                },
+            },
+        },
+    });
+
+    let event = from_json_value::<OriginalSyncRoomMessageEvent>(json).unwrap();
+    let relations = &event.unsigned.relations;
+
+    assert_matches!(relations.replace(), None);
+    assert_eq!(relations.thread().unwrap().count, uint!(7));
+    let latest_event = relations.latest_thread_event().unwrap().deserialize().unwrap();
+    assert_eq!(latest_event.event_id(), "$latest");
+    let references = relations.references();
+    assert_eq!(references.len(), 2);
+    assert_eq!(references[0].event_id, "$ref1");
+}
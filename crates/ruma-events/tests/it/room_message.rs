@@ -3,7 +3,7 @@ use std::{borrow::Cow, collections::BTreeSet};
 use assert_matches2::assert_matches;
 use js_int::uint;
 use ruma_common::{
-    mxc_uri, owned_event_id, owned_room_id, owned_user_id, room_id,
+    event_id, mxc_uri, owned_event_id, owned_room_id, owned_user_id, room_id,
     serde::{Base64, Raw},
     user_id, MilliSecondsSinceUnixEpoch, OwnedDeviceId,
 };
@@ -13,9 +13,9 @@ use ruma_events::{
         message::{
             AddMentions, AudioMessageEventContent, EmoteMessageEventContent,
             FileMessageEventContent, ForwardThread, ImageMessageEventContent,
-            KeyVerificationRequestEventContent, MessageType, OriginalRoomMessageEvent,
-            OriginalSyncRoomMessageEvent, Relation, ReplyWithinThread, RoomMessageEventContent,
-            TextMessageEventContent, VideoMessageEventContent,
+            KeyVerificationRequestEventContent, MessageType, MessageTypeKind,
+            OriginalRoomMessageEvent, OriginalSyncRoomMessageEvent, Relation, ReplyWithinThread,
+            RoomMessageEventContent, TextMessageEventContent, VideoMessageEventContent,
         },
         EncryptedFileInit, JsonWebKeyInit, MediaSource,
     },
@@ -70,7 +70,12 @@ fn custom_msgtype_deserialization() {
 
     assert_eq!(custom_event.msgtype(), "my_custom_msgtype");
     assert_eq!(custom_event.body(), "my custom message");
-    assert_eq!(custom_event.data(), Cow::Owned(expected_json_data));
+    assert_eq!(custom_event.data(), Cow::Owned(expected_json_data.clone()));
+    assert_eq!(custom_event.custom_data(), Some(&expected_json_data));
+
+    let content = RoomMessageEventContent::new(custom_event);
+    assert_eq!(content.custom_data(), Some(&expected_json_data));
+    assert_eq!(RoomMessageEventContent::text_plain("text").custom_data(), None);
 }
 
 #[test]
@@ -233,6 +238,63 @@ fn markdown_options() {
     assert_eq!(formatted_body.unwrap().body, "<p>A message with a <del>strike</del></p>\n");
 }
 
+#[test]
+#[cfg(feature = "html")]
+fn text_msgtype_from_html() {
+    let content = RoomMessageEventContent::text_from_html(
+        "<p>Hello, <em>World</em>!</p><p><removed>Bye</removed></p>",
+    );
+
+    assert_matches!(content.msgtype, MessageType::Text(text));
+    assert_eq!(text.body, "Hello, World!\nBye");
+    assert_eq!(text.formatted.unwrap().body, "<p>Hello, <em>World</em>!</p><p>Bye</p>");
+}
+
+#[test]
+#[cfg(feature = "html")]
+fn notice_and_emote_msgtypes_from_html() {
+    let content =
+        RoomMessageEventContent::notice_from_html("<b>Server</b> <removed>restarted</removed>");
+    assert_matches!(content.msgtype, MessageType::Notice(notice));
+    assert_eq!(notice.body, "Server restarted");
+    assert_eq!(notice.formatted.unwrap().body, "<b>Server</b> restarted");
+
+    let content = RoomMessageEventContent::emote_from_html("waves <em>hello</em>");
+    assert_matches!(content.msgtype, MessageType::Emote(emote));
+    assert_eq!(emote.body, "waves hello");
+    assert_eq!(emote.formatted.unwrap().body, "waves <em>hello</em>");
+}
+
+#[test]
+#[cfg(feature = "limit-message-body-len")]
+fn body_too_long_deserialization() {
+    use ruma_events::room::message::MAX_BODY_LEN;
+
+    let json_data = json!({
+        "body": "a".repeat(MAX_BODY_LEN),
+        "msgtype": "m.text",
+    });
+    from_json_value::<RoomMessageEventContent>(json_data).unwrap();
+
+    let json_data = json!({
+        "body": "a".repeat(MAX_BODY_LEN + 1),
+        "msgtype": "m.text",
+    });
+    let raw = serde_json::value::to_raw_value(&json_data).unwrap();
+    RoomMessageEventContent::deserialize_body_only(&raw).unwrap_err();
+    from_json_value::<RoomMessageEventContent>(json_data).unwrap_err();
+}
+
+#[test]
+#[cfg(not(feature = "limit-message-body-len"))]
+fn long_body_deserialization() {
+    let json_data = json!({
+        "body": "a".repeat(65_537),
+        "msgtype": "m.text",
+    });
+    from_json_value::<RoomMessageEventContent>(json_data).unwrap();
+}
+
 #[test]
 fn verification_request_msgtype_deserialization() {
     let user_id = user_id!("@example2:localhost");
@@ -293,7 +355,8 @@ fn content_deserialization_failure() {
         "msgtype": "m.location",
         "url": "http://example.com/audio.mp3"
     });
-    assert_matches!(from_json_value::<RoomMessageEventContent>(json_data), Err(_));
+    assert_matches!(from_json_value::<RoomMessageEventContent>(json_data), Err(err));
+    assert!(err.to_string().starts_with("failed to parse m.location content: "), "{err}");
 }
 
 #[test]
@@ -482,6 +545,118 @@ fn reply_thread_fallback() {
     assert!(thread_info.is_falling_back);
 }
 
+#[test]
+fn from_slash_command() {
+    assert_matches!(
+        RoomMessageEventContent::from_slash_command("/me waves").msgtype,
+        MessageType::Emote(EmoteMessageEventContent { body, .. })
+    );
+    assert_eq!(body, "waves");
+
+    let content = RoomMessageEventContent::from_slash_command("/notice Server restart");
+    assert_eq!(content.kind(), MessageTypeKind::Notice);
+    assert_eq!(content.body(), "Server restart");
+
+    let content = RoomMessageEventContent::from_slash_command("/plain /me is not a command");
+    assert_eq!(content.kind(), MessageTypeKind::Text);
+    assert_eq!(content.body(), "/me is not a command");
+
+    let content = RoomMessageEventContent::from_slash_command("/unknown command");
+    assert_eq!(content.kind(), MessageTypeKind::Text);
+    assert_eq!(content.body(), "/unknown command");
+
+    let content = RoomMessageEventContent::from_slash_command("Hello");
+    assert_eq!(content.kind(), MessageTypeKind::Text);
+    assert_eq!(content.body(), "Hello");
+}
+
+#[test]
+fn message_type_kind() {
+    assert_eq!(RoomMessageEventContent::text_plain("Hello").kind(), MessageTypeKind::Text);
+    assert_eq!(RoomMessageEventContent::notice_plain("Hello").kind(), MessageTypeKind::Notice);
+    assert_eq!(
+        RoomMessageEventContent::new(MessageType::Image(ImageMessageEventContent::plain(
+            "image.png".to_owned(),
+            mxc_uri!("mxc://example.org/image").to_owned(),
+        )))
+        .kind(),
+        MessageTypeKind::Image
+    );
+
+    let custom =
+        MessageType::new("org.example.custom", "Hello".to_owned(), Default::default()).unwrap();
+    assert_eq!(custom.kind(), MessageTypeKind::Custom);
+}
+
+#[test]
+fn all_known_msgtypes() {
+    for msgtype in MessageType::ALL_KNOWN {
+        let kind = MessageTypeKind::from_msgtype(msgtype);
+        assert_ne!(kind, MessageTypeKind::Custom, "{msgtype}");
+
+        // Known message types have required fields, so they either fail to deserialize or
+        // deserialize to a known variant.
+        if let Ok(message_type) = MessageType::new(msgtype, "Hello".to_owned(), Default::default())
+        {
+            assert_eq!(message_type.kind(), kind, "{msgtype}");
+            assert_eq!(message_type.msgtype(), *msgtype);
+        }
+    }
+    assert_eq!(MessageTypeKind::from_msgtype("org.example.custom"), MessageTypeKind::Custom);
+
+    assert_eq!(MessageType::ALL_KNOWN.len(), 10);
+    assert!(MessageType::ALL_KNOWN.contains(&RoomMessageEventContent::text_plain("").msgtype()));
+}
+
+#[test]
+fn for_thread_replaces_relation() {
+    let content = RoomMessageEventContent::text_plain("Threaded message")
+        .make_reply_to(
+            &OriginalRoomMessageEvent {
+                content: RoomMessageEventContent::text_plain("Replied to"),
+                event_id: owned_event_id!("$replied_to"),
+                origin_server_ts: MilliSecondsSinceUnixEpoch(uint!(10_000)),
+                room_id: owned_room_id!("!testroomid:example.org"),
+                sender: owned_user_id!("@user:example.org"),
+                unsigned: MessageLikeUnsigned::default(),
+            },
+            ForwardThread::No,
+            AddMentions::No,
+        )
+        .for_thread(owned_event_id!("$thread_root"), owned_event_id!("$latest_event"));
+
+    assert_matches!(content.relates_to, Some(Relation::Thread(thread)));
+    assert_eq!(thread.event_id, "$thread_root");
+    assert_eq!(thread.in_reply_to.unwrap().event_id, "$latest_event");
+    assert!(thread.is_falling_back);
+}
+
+#[test]
+fn body_eq_ignores_fallbacks() {
+    let original_message = OriginalRoomMessageEvent {
+        content: RoomMessageEventContent::text_plain("Original message"),
+        event_id: owned_event_id!("$original_message"),
+        origin_server_ts: MilliSecondsSinceUnixEpoch(uint!(10_000)),
+        room_id: owned_room_id!("!testroomid:example.org"),
+        sender: owned_user_id!("@user:example.org"),
+        unsigned: MessageLikeUnsigned::default(),
+    };
+    let plain = RoomMessageEventContent::text_plain("Hello World!");
+
+    let reply = RoomMessageEventContent::text_html("Hello World!", "Hello <em>World</em>!")
+        .make_reply_to(&original_message, ForwardThread::No, AddMentions::No);
+    assert_ne!(reply.body(), plain.body());
+    assert!(reply.body_eq(&plain));
+
+    let replacement = RoomMessageEventContent::text_plain(" Hello World!\n")
+        .make_replacement(&original_message, None);
+    assert_ne!(replacement.body(), plain.body());
+    assert!(replacement.body_eq(&plain));
+    assert!(replacement.body_eq(&reply));
+
+    assert!(!plain.body_eq(&RoomMessageEventContent::text_plain("Hello World?")));
+}
+
 #[test]
 fn reply_add_mentions() {
     let user = owned_user_id!("@user:example.org");
@@ -761,6 +936,38 @@ fn make_replacement_no_reply() {
     assert_matches!(content.mentions, None);
 }
 
+#[test]
+fn make_replacement_of_replacement() {
+    let content = RoomMessageEventContent::text_plain("Third version");
+
+    let edit_json = json!({
+        "content": {
+            "body": "* Second version",
+            "msgtype": "m.text",
+            "m.new_content": {
+                "body": "Second version",
+                "msgtype": "m.text",
+            },
+            "m.relates_to": {
+                "rel_type": "m.replace",
+                "event_id": "$original",
+            },
+        },
+        "event_id": "$edit",
+        "origin_server_ts": 134_829_848,
+        "sender": "@user:notareal.hs",
+        "type": "m.room.message",
+    });
+    let edit: OriginalSyncRoomMessageEvent = from_json_value(edit_json).unwrap();
+
+    let content = content.make_replacement(&edit, None);
+
+    assert_matches!(content.msgtype, MessageType::Text(TextMessageEventContent { body, .. }));
+    assert_eq!(body, "* Third version");
+    assert_matches!(content.relates_to, Some(Relation::Replacement(replacement)));
+    assert_eq!(replacement.event_id, "$original");
+}
+
 #[test]
 fn make_replacement_with_reply() {
     let replied_to_message = OriginalRoomMessageEvent {
@@ -1230,3 +1437,111 @@ fn mentions_room_deserialization() {
     let mentions = content.mentions.unwrap();
     assert!(mentions.room);
 }
+
+#[test]
+fn in_reply_to() {
+    let content = RoomMessageEventContent::text_plain("Hello");
+    assert_eq!(content.in_reply_to(), None);
+
+    let json_data = json!({
+        "body": "Reply",
+        "msgtype": "m.text",
+        "m.relates_to": {
+            "m.in_reply_to": {
+                "event_id": "$replied_to",
+            },
+        },
+    });
+    let content = from_json_value::<RoomMessageEventContent>(json_data).unwrap();
+    assert_eq!(content.in_reply_to(), Some(event_id!("$replied_to")));
+
+    let json_data = json!({
+        "body": "Reply in thread",
+        "msgtype": "m.text",
+        "m.relates_to": {
+            "rel_type": "m.thread",
+            "event_id": "$thread_root",
+            "m.in_reply_to": {
+                "event_id": "$replied_to",
+            },
+        },
+    });
+    let content = from_json_value::<RoomMessageEventContent>(json_data).unwrap();
+    assert_eq!(content.in_reply_to(), Some(event_id!("$replied_to")));
+
+    let json_data = json!({
+        "body": "Message in thread",
+        "msgtype": "m.text",
+        "m.relates_to": {
+            "rel_type": "m.thread",
+            "event_id": "$thread_root",
+            "m.in_reply_to": {
+                "event_id": "$latest_event",
+            },
+            "is_falling_back": true,
+        },
+    });
+    let content = from_json_value::<RoomMessageEventContent>(json_data).unwrap();
+    assert_eq!(content.in_reply_to(), None);
+
+    let json_data = json!({
+        "body": "Message in thread",
+        "msgtype": "m.text",
+        "m.relates_to": {
+            "rel_type": "m.thread",
+            "event_id": "$thread_root",
+        },
+    });
+    let content = from_json_value::<RoomMessageEventContent>(json_data).unwrap();
+    assert_eq!(content.in_reply_to(), None);
+}
+
+#[test]
+fn estimated_serialized_len() {
+    let content = RoomMessageEventContent::text_html("Hello \"world\" 👋", "<b>Hello</b> world")
+        .add_mentions(Mentions::with_user_ids(vec![owned_user_id!("@alice:example.org")]));
+
+    assert_eq!(content.estimated_serialized_len(), serde_json::to_string(&content).unwrap().len());
+}
+
+#[test]
+fn deserialize_body_only() {
+    let json = serde_json::value::to_raw_value(&json!({
+        "body": "Hello",
+        "format": "org.matrix.custom.html",
+        "formatted_body": "<b>Hello</b>",
+        "msgtype": "m.notice",
+        "m.relates_to": {
+            "m.in_reply_to": {
+                "event_id": "$replied_to",
+            },
+        },
+    }))
+    .unwrap();
+    let (kind, body) = RoomMessageEventContent::deserialize_body_only(&json).unwrap();
+    assert_eq!(kind, MessageTypeKind::Notice);
+    assert_eq!(body, "Hello");
+
+    let json = serde_json::value::to_raw_value(&json!({
+        "body": "Custom",
+        "msgtype": "org.example.custom",
+        "custom_field": 42,
+    }))
+    .unwrap();
+    let (kind, body) = RoomMessageEventContent::deserialize_body_only(&json).unwrap();
+    assert_eq!(kind, MessageTypeKind::Custom);
+    assert_eq!(body, "Custom");
+
+    let json = serde_json::value::to_raw_value(&json!({ "msgtype": "m.text" })).unwrap();
+    RoomMessageEventContent::deserialize_body_only(&json).unwrap_err();
+}
+
+#[test]
+fn msgtype_conversions() {
+    let msgtype = MessageType::from(TextMessageEventContent::plain("Hello"));
+    assert_eq!(msgtype.kind(), MessageTypeKind::Text);
+
+    assert_matches!(EmoteMessageEventContent::try_from(msgtype), Err(msgtype));
+    let text = TextMessageEventContent::try_from(msgtype).unwrap();
+    assert_eq!(text.body, "Hello");
+}
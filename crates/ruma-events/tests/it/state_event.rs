@@ -2,8 +2,12 @@ use assert_matches2::assert_matches;
 use js_int::uint;
 use ruma_common::{mxc_uri, room_alias_id, serde::CanBeEmpty, MilliSecondsSinceUnixEpoch};
 use ruma_events::{
-    room::aliases::RoomAliasesEventContent, AnyStateEvent, AnySyncStateEvent, AnyTimelineEvent,
-    StateEvent, SyncStateEvent,
+    room::{
+        aliases::RoomAliasesEventContent, create::RoomCreateEventContent,
+        third_party_invite::RoomThirdPartyInviteEventContent,
+    },
+    space::child::SpaceChildEventContent,
+    AnyStateEvent, AnySyncStateEvent, AnyTimelineEvent, StateEvent, SyncStateEvent,
 };
 use serde_json::{from_value as from_json_value, json, Value as JsonValue};
 
@@ -173,3 +177,45 @@ fn deserialize_full_event_convert_to_sync() {
     );
     assert_eq!(sync_ev.sender, "@carl:example.com");
 }
+
+#[test]
+fn state_key_str() {
+    let create_ev = from_json_value::<SyncStateEvent<RoomCreateEventContent>>(json!({
+        "content": { "room_version": "11" },
+        "event_id": "$create:example.com",
+        "origin_server_ts": 1,
+        "sender": "@carl:example.com",
+        "state_key": "",
+        "type": "m.room.create",
+    }))
+    .unwrap();
+    assert_eq!(create_ev.state_key_str(), "");
+
+    let space_child_ev = from_json_value::<StateEvent<SpaceChildEventContent>>(json!({
+        "content": { "via": ["example.com"] },
+        "event_id": "$child:example.com",
+        "origin_server_ts": 1,
+        "room_id": "!space:example.com",
+        "sender": "@carl:example.com",
+        "state_key": "!child:example.com",
+        "type": "m.space.child",
+    }))
+    .unwrap();
+    assert_eq!(space_child_ev.state_key_str(), "!child:example.com");
+
+    let third_party_invite_ev =
+        from_json_value::<SyncStateEvent<RoomThirdPartyInviteEventContent>>(json!({
+            "content": {
+                "display_name": "Alice",
+                "key_validity_url": "https://example.com/validity",
+                "public_key": "abc123",
+            },
+            "event_id": "$invite:example.com",
+            "origin_server_ts": 1,
+            "sender": "@carl:example.com",
+            "state_key": "invite_token",
+            "type": "m.room.third_party_invite",
+        }))
+        .unwrap();
+    assert_eq!(third_party_invite_ev.state_key_str(), "invite_token");
+}
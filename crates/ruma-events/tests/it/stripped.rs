@@ -1,9 +1,10 @@
 use assert_matches2::assert_matches;
 use js_int::uint;
-use ruma_common::mxc_uri;
+use ruma_common::{mxc_uri, owned_room_id, owned_user_id, server_name};
 use ruma_events::{
     room::{join_rules::JoinRule, topic::RoomTopicEventContent},
-    AnyStrippedStateEvent,
+    space::child::SpaceChildEventContent,
+    AnyStrippedStateEvent, EmptyStateKey, StateEventContent,
 };
 use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
@@ -82,3 +83,25 @@ fn deserialize_stripped_state_events() {
     assert_eq!(image_info.size, Some(uint!(1024)));
     assert_eq!(image_info.thumbnail_info.unwrap().size, Some(uint!(32)));
 }
+
+#[test]
+fn state_event_content_into_stripped() {
+    let stripped = RoomTopicEventContent::new("Testing room".into())
+        .into_stripped(owned_user_id!("@example:localhost"), EmptyStateKey);
+    assert_eq!(
+        to_json_value(&stripped).unwrap(),
+        json!({
+            "type": "m.room.topic",
+            "content": { "topic": "Testing room" },
+            "sender": "@example:localhost",
+            "state_key": "",
+        })
+    );
+
+    let stripped = SpaceChildEventContent::new(vec![server_name!("localhost").to_owned()])
+        .into_stripped(owned_user_id!("@example:localhost"), owned_room_id!("!child:localhost"));
+    assert_matches!(stripped.deserialize().unwrap(), AnyStrippedStateEvent::SpaceChild(ev));
+    assert_eq!(ev.sender, "@example:localhost");
+    assert_eq!(ev.state_key, "!child:localhost");
+    assert_eq!(ev.content.via.unwrap(), ["localhost"]);
+}
@@ -12,6 +12,27 @@ use tracing::debug;
 
 use crate::SanitizerConfig;
 
+/// HTML elements that are rendered on their own line.
+const BLOCK_ELEMENTS: &[&str] = &[
+    "blockquote",
+    "details",
+    "div",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "table",
+    "tr",
+    "ul",
+];
+
 /// An HTML fragment.
 ///
 /// To get the serialized HTML, use its `Display` implementation. Due to the fact that the HTML is
@@ -52,6 +73,19 @@ impl Html {
         config.clean(self);
     }
 
+    /// Get the plain text representation of this HTML.
+    ///
+    /// Only the content of text nodes is kept. A line break is inserted for `<br>` tags and around
+    /// block elements like `<p>`.
+    pub fn to_plain_text(&self) -> String {
+        let mut text = String::new();
+        self.nodes[0].push_plain_text(self, &mut text);
+
+        let len = text.trim_end_matches('\n').len();
+        text.truncate(len);
+        text
+    }
+
     /// Construct a new `Node` with the given data and add it to this `Html`.
     ///
     /// Returns the index of the new node.
@@ -326,6 +360,34 @@ impl Node {
 }
 
 impl Node {
+    fn push_plain_text(&self, fragment: &Html, text: &mut String) {
+        match &self.data {
+            NodeData::Text(t) => text.push_str(t),
+            NodeData::Element(data) if data.name.local == local_name!("br") => text.push('\n'),
+            NodeData::Element(_) | NodeData::Document => {
+                let is_block = self
+                    .as_element()
+                    .is_some_and(|data| BLOCK_ELEMENTS.contains(&&*data.name.local));
+
+                if is_block && !text.is_empty() && !text.ends_with('\n') {
+                    text.push('\n');
+                }
+
+                let mut next_child = self.first_child;
+                while let Some(child) = next_child {
+                    let child = &fragment.nodes[child];
+                    child.push_plain_text(fragment, text);
+                    next_child = child.next_sibling;
+                }
+
+                if is_block && !text.ends_with('\n') {
+                    text.push('\n');
+                }
+            }
+            NodeData::Other => {}
+        }
+    }
+
     pub(crate) fn serialize<S>(&self, fragment: &Html, serializer: &mut S) -> io::Result<()>
     where
         S: Serializer,
@@ -408,4 +470,21 @@ mod tests {
 
         assert_eq!(Html::parse("").to_string(), "");
     }
+
+    #[test]
+    fn plain_text() {
+        let html = "\
+            <h1>Title</h1>\
+            <div>\
+                <p>This is some <em>text</em></p>\
+                <p>First line<br>Second line</p>\
+            </div>\
+        ";
+        assert_eq!(
+            Html::parse(html).to_plain_text(),
+            "Title\nThis is some text\nFirst line\nSecond line"
+        );
+
+        assert_eq!(Html::parse("").to_plain_text(), "");
+    }
 }
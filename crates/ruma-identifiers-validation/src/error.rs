@@ -14,6 +14,16 @@ pub enum Error {
     #[error("identifier contains invalid characters")]
     InvalidCharacters,
 
+    /// The identifier contains an invalid character at the given byte offset.
+    #[error("identifier contains invalid character {character:?} at index {index}")]
+    InvalidCharacter {
+        /// The invalid character.
+        character: char,
+
+        /// The byte offset of the invalid character in the identifier.
+        index: u32,
+    },
+
     /// The string isn't a valid Matrix ID.
     #[error("invalid matrix ID: {0}")]
     InvalidMatrixId(#[from] MatrixIdError),
@@ -56,6 +66,28 @@ pub enum Error {
     MissingLeadingSigil,
 }
 
+impl Error {
+    /// Creates an [`Error::InvalidCharacter`] for the first character of `s` that doesn't match
+    /// `is_valid`, or `None` if all characters are valid.
+    pub(crate) fn find_invalid_character(s: &str, is_valid: impl Fn(char) -> bool) -> Option<Self> {
+        s.char_indices()
+            .find(|&(_, c)| !is_valid(c))
+            .map(|(index, character)| Self::InvalidCharacter { character, index: index as u32 })
+    }
+
+    /// Adds `offset` to the byte offset of an [`Error::InvalidCharacter`].
+    ///
+    /// This is useful when a part of an identifier was validated on its own.
+    pub(crate) fn with_offset(self, offset: usize) -> Self {
+        match self {
+            Self::InvalidCharacter { character, index } => {
+                Self::InvalidCharacter { character, index: index + offset as u32 }
+            }
+            error => error,
+        }
+    }
+}
+
 impl From<Utf8Error> for Error {
     fn from(_: Utf8Error) -> Self {
         Self::InvalidUtf8
@@ -172,4 +204,17 @@ mod tests {
     fn small_error_type() {
         assert!(size_of::<Error>() <= 8);
     }
+
+    #[test]
+    #[cfg(not(any(feature = "compat-key-id", feature = "compat-user-id")))]
+    fn invalid_character_position() {
+        let error = crate::key_id::validate("ed25519:ab/c").unwrap_err();
+        assert_eq!(error, Error::InvalidCharacter { character: '/', index: 10 });
+        assert_eq!(error.to_string(), "identifier contains invalid character '/' at index 10");
+
+        assert_eq!(
+            crate::user_id::validate("@aé b:example.org"),
+            Err(Error::InvalidCharacter { character: 'é', index: 2 })
+        );
+    }
 }
@@ -7,7 +7,8 @@ pub fn validate(s: &str) -> Result<NonZeroU8, Error> {
         NonZeroU8::new(s.find(':').ok_or(Error::MissingColon)? as u8).ok_or(Error::MissingColon)?;
 
     #[cfg(not(feature = "compat-key-id"))]
-    validate_version(&s[colon_idx.get() as usize + 1..])?;
+    validate_version(&s[colon_idx.get() as usize + 1..])
+        .map_err(|e| e.with_offset(colon_idx.get() as usize + 1))?;
 
     Ok(colon_idx)
 }
@@ -16,8 +17,10 @@ pub fn validate(s: &str) -> Result<NonZeroU8, Error> {
 fn validate_version(version: &str) -> Result<(), Error> {
     if version.is_empty() {
         return Err(Error::Empty);
-    } else if !version.chars().all(|c| c.is_alphanumeric() || c == '_') {
-        return Err(Error::InvalidCharacters);
+    } else if let Some(error) =
+        Error::find_invalid_character(version, |c| c.is_alphanumeric() || c == '_')
+    {
+        return Err(error);
     }
 
     Ok(())
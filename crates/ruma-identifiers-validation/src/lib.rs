@@ -21,6 +21,51 @@ pub use error::Error;
 #[cfg(not(feature = "compat-arbitrary-length-ids"))]
 const MAX_BYTES: usize = 255;
 
+/// Options to validate an identifier at runtime, regardless of the enabled cargo features.
+///
+/// This allows to use different rules for different identifiers in the same binary, e.g.
+/// depending on the room version.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct ValidationOptions {
+    /// The maximum length of the identifier in bytes, if any.
+    pub max_bytes: Option<usize>,
+
+    /// Whether the identifier must contain a colon followed by a valid server name.
+    pub require_server_name: bool,
+}
+
+impl ValidationOptions {
+    /// Options following the specification: the identifier must be 255 bytes or less and must
+    /// contain a server name.
+    pub fn strict() -> Self {
+        Self { max_bytes: Some(255), require_server_name: true }
+    }
+
+    /// Options that only check the leading sigil of the identifier.
+    pub fn lenient() -> Self {
+        Self { max_bytes: None, require_server_name: false }
+    }
+}
+
+/// Checks if an identifier is valid according to the given options.
+fn validate_id_with(id: &str, first_byte: u8, options: &ValidationOptions) -> Result<(), Error> {
+    if options.max_bytes.is_some_and(|max_bytes| id.len() > max_bytes) {
+        return Err(Error::MaximumLengthExceeded);
+    }
+
+    if id.as_bytes().first() != Some(&first_byte) {
+        return Err(Error::MissingLeadingSigil);
+    }
+
+    if options.require_server_name {
+        let colon_idx = id.find(':').ok_or(Error::MissingColon)?;
+        server_name::validate(&id[colon_idx + 1..])?;
+    }
+
+    Ok(())
+}
+
 /// Checks if an identifier is valid.
 fn validate_id(id: &str, first_byte: u8) -> Result<(), Error> {
     #[cfg(not(feature = "compat-arbitrary-length-ids"))]
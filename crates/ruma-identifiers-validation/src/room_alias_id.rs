@@ -1,5 +1,9 @@
-use crate::{validate_delimited_id, Error};
+use crate::{validate_delimited_id, validate_id_with, Error, ValidationOptions};
 
 pub fn validate(s: &str) -> Result<(), Error> {
     validate_delimited_id(s, b'#')
 }
+
+pub fn validate_with(s: &str, options: &ValidationOptions) -> Result<(), Error> {
+    validate_id_with(s, b'#', options)
+}
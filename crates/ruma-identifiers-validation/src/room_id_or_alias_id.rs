@@ -1,4 +1,4 @@
-use crate::Error;
+use crate::{Error, ValidationOptions};
 
 pub fn validate(s: &str) -> Result<(), Error> {
     match s.as_bytes().first() {
@@ -7,3 +7,37 @@ pub fn validate(s: &str) -> Result<(), Error> {
         _ => Err(Error::MissingLeadingSigil),
     }
 }
+
+pub fn validate_with(s: &str, options: &ValidationOptions) -> Result<(), Error> {
+    match s.as_bytes().first() {
+        Some(b'#') => crate::room_alias_id::validate_with(s, options),
+        Some(b'!') => crate::room_id::validate_with(s, options),
+        _ => Err(Error::MissingLeadingSigil),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate_with;
+    use crate::{Error, ValidationOptions};
+
+    #[test]
+    fn validate_with_options() {
+        let strict = ValidationOptions::strict();
+        let lenient = ValidationOptions::lenient();
+
+        assert_eq!(validate_with("!room:example.org", &strict), Ok(()));
+        assert_eq!(validate_with("#alias:example.org", &strict), Ok(()));
+        assert_eq!(validate_with("!room", &strict), Err(Error::MissingColon));
+        assert_eq!(validate_with("#alias:", &strict), Err(Error::InvalidServerName));
+        assert_eq!(
+            validate_with(&format!("!{}:example.org", "a".repeat(255)), &strict),
+            Err(Error::MaximumLengthExceeded)
+        );
+
+        assert_eq!(validate_with("!room", &lenient), Ok(()));
+        assert_eq!(validate_with("#alias", &lenient), Ok(()));
+        assert_eq!(validate_with(&format!("!{}", "a".repeat(255)), &lenient), Ok(()));
+        assert_eq!(validate_with("@user:example.org", &lenient), Err(Error::MissingLeadingSigil));
+    }
+}
@@ -8,8 +8,10 @@ pub fn validate(s: &str) -> Result<(), Error> {
         Err(Error::Empty)
     } else if s.chars().count() > MAX_CODE_POINTS {
         Err(Error::MaximumLengthExceeded)
-    } else if !s.chars().all(|c| c.is_alphanumeric() || ".-".contains(c)) {
-        Err(Error::InvalidCharacters)
+    } else if let Some(error) =
+        Error::find_invalid_character(s, |c| c.is_alphanumeric() || ".-".contains(c))
+    {
+        Err(error)
     } else {
         Ok(())
     }
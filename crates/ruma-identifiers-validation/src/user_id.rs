@@ -3,7 +3,7 @@ use crate::{parse_id, Error};
 pub fn validate(s: &str) -> Result<(), Error> {
     let colon_idx = parse_id(s, b'@')?;
     let localpart = &s[1..colon_idx];
-    let _ = localpart_is_fully_conforming(localpart)?;
+    let _ = localpart_is_fully_conforming(localpart).map_err(|e| e.with_offset(1))?;
 
     Ok(())
 }
@@ -13,6 +13,9 @@ pub fn validate(s: &str) -> Result<(), Error> {
 /// Returns an `Err` for invalid user ID localparts, `Ok(false)` for historical user ID localparts
 /// and `Ok(true)` for fully conforming user ID localparts.
 ///
+/// If the localpart contains an invalid character, the returned error contains its byte offset in
+/// the localpart.
+///
 /// With the `compat` feature enabled, this will also return `Ok(false)` for invalid user ID
 /// localparts. User IDs that don't even meet the historical user ID restrictions exist in the wild
 /// due to Synapse allowing them over federation. This will likely be fixed in an upcoming room
@@ -29,17 +32,24 @@ pub fn localpart_is_fully_conforming(localpart: &str) -> Result<bool, Error> {
         // for historical user IDs, or is empty. If that's the case, return an error.
         // See https://spec.matrix.org/latest/appendices/#historical-user-ids
         #[cfg(not(feature = "compat-user-id"))]
-        let is_invalid =
-            localpart.is_empty() || localpart.bytes().any(|b| b < 0x21 || b == b':' || b > 0x7E);
+        {
+            if localpart.is_empty() {
+                return Err(Error::InvalidCharacters);
+            }
+
+            if let Some(error) = Error::find_invalid_character(localpart, |c| {
+                matches!(c, '\u{21}'..='\u{7E}') && c != ':'
+            }) {
+                return Err(error);
+            }
+        }
 
         // In compat mode, allow anything except `:` to match Synapse. The `:` check is only needed
         // because this function can be called through `UserId::parse_with_servername`, otherwise
         // it would be impossible for the input to contain a `:`.
         #[cfg(feature = "compat-user-id")]
-        let is_invalid = localpart.as_bytes().contains(&b':');
-
-        if is_invalid {
-            return Err(Error::InvalidCharacters);
+        if let Some(error) = Error::find_invalid_character(localpart, |c| c != ':') {
+            return Err(error);
         }
     }
 
@@ -41,6 +41,7 @@
 //! * `rand`
 //! * `markdown`
 //! * `html`
+//! * `media-integrity`
 //!
 //! # Unstable features
 //!